@@ -179,10 +179,22 @@ fn interpret_input(input: &str, sys: &mut System) -> bool {
             }
         }
         "memory" => {
-            writeln!(&mut io::stdout(), "total memory: {} KB", sys.total_memory());
-            writeln!(&mut io::stdout(), "used memory : {} KB", sys.used_memory());
-            writeln!(&mut io::stdout(), "total swap  : {} KB", sys.total_swap());
-            writeln!(&mut io::stdout(), "used swap   : {} KB", sys.used_swap());
+            writeln!(
+                &mut io::stdout(),
+                "total memory: {} bytes",
+                sys.total_memory()
+            );
+            writeln!(
+                &mut io::stdout(),
+                "used memory : {} bytes",
+                sys.used_memory()
+            );
+            writeln!(
+                &mut io::stdout(),
+                "total swap  : {} bytes",
+                sys.total_swap()
+            );
+            writeln!(&mut io::stdout(), "used swap   : {} bytes", sys.used_swap());
         }
         "quit" | "exit" => return true,
         "all" => {
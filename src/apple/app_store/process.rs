@@ -1,8 +1,10 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use std::collections::HashMap;
 use std::path::Path;
+use std::time::{Duration, SystemTime};
 
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal, StaleFile, Uid};
 
 #[doc = include_str!("../../../md_doc/process.md")]
 #[derive(Clone)]
@@ -17,6 +19,14 @@ impl ProcessExt for Process {
         false
     }
 
+    fn stale_files(&self) -> Vec<StaleFile> {
+        Vec::new()
+    }
+
+    fn numa_memory(&self) -> HashMap<usize, u64> {
+        HashMap::new()
+    }
+
     fn name(&self) -> &str {
         ""
     }
@@ -65,10 +75,46 @@ impl ProcessExt for Process {
         0
     }
 
+    fn started_at(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH
+    }
+
+    fn run_time(&self) -> Duration {
+        Duration::default()
+    }
+
     fn cpu_usage(&self) -> f32 {
         0.0
     }
 
+    fn user_cpu_usage(&self) -> f32 {
+        0.0
+    }
+
+    fn system_cpu_usage(&self) -> f32 {
+        0.0
+    }
+
+    fn total_cpu_time(&self) -> Duration {
+        Duration::default()
+    }
+
+    fn thread_count(&self) -> usize {
+        0
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn cgroup(&self) -> &Path {
+        Path::new("")
+    }
+
+    fn is_kernel_thread(&self) -> bool {
+        false
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage::default()
     }
@@ -0,0 +1,10 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::Cgroup;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub(crate) fn refresh_cgroups_list(_cgroups: &mut HashMap<PathBuf, Cgroup>) {}
+
+pub(crate) fn refresh_cgroups(_cgroups: &mut HashMap<PathBuf, Cgroup>) {}
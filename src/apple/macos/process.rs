@@ -1,15 +1,19 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::mem::{self, MaybeUninit};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use std::borrow::Borrow;
 
 use libc::{c_int, c_void, gid_t, kill, size_t, uid_t};
 
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{
+    DiskUsage, Pid, ProcessExt, ProcessRefreshKind, ProcessStatus, Signal, StaleFile, Uid,
+};
 
 use crate::sys::process::ThreadStatus;
 use crate::sys::system::Wrap;
@@ -29,9 +33,14 @@ pub struct Process {
     pub(crate) virtual_memory: u64,
     old_utime: u64,
     old_stime: u64,
+    /// User part of the task time, only used by the "backup way" of CPU computation.
+    old_user_time: u64,
     start_time: u64,
     updated: bool,
     cpu_usage: f32,
+    user_cpu_usage: f32,
+    system_cpu_usage: f32,
+    total_cpu_time: u64,
     /// User id of the process owner.
     pub uid: uid_t,
     /// Group id of the process owner.
@@ -46,6 +55,7 @@ pub struct Process {
     pub(crate) old_written_bytes: u64,
     pub(crate) read_bytes: u64,
     pub(crate) written_bytes: u64,
+    thread_count: usize,
 }
 
 impl Process {
@@ -62,8 +72,12 @@ impl Process {
             memory: 0,
             virtual_memory: 0,
             cpu_usage: 0.,
+            user_cpu_usage: 0.,
+            system_cpu_usage: 0.,
+            total_cpu_time: 0,
             old_utime: 0,
             old_stime: 0,
+            old_user_time: 0,
             updated: true,
             start_time: 0,
             uid: 0,
@@ -74,6 +88,7 @@ impl Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            thread_count: 0,
         }
     }
 
@@ -101,8 +116,12 @@ impl Process {
             memory: 0,
             virtual_memory: 0,
             cpu_usage: 0.,
+            user_cpu_usage: 0.,
+            system_cpu_usage: 0.,
+            total_cpu_time: 0,
             old_utime: 0,
             old_stime: 0,
+            old_user_time: 0,
             updated: true,
             start_time,
             uid: 0,
@@ -113,6 +132,7 @@ impl Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            thread_count: 0,
         }
     }
 }
@@ -131,8 +151,12 @@ impl ProcessExt for Process {
             memory: 0,
             virtual_memory: 0,
             cpu_usage: 0.,
+            user_cpu_usage: 0.,
+            system_cpu_usage: 0.,
+            total_cpu_time: 0,
             old_utime: 0,
             old_stime: 0,
+            old_user_time: 0,
             updated: true,
             start_time,
             uid: 0,
@@ -143,6 +167,7 @@ impl ProcessExt for Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            thread_count: 0,
         }
     }
 
@@ -186,6 +211,14 @@ impl ProcessExt for Process {
         unsafe { kill(self.pid, c_signal) == 0 }
     }
 
+    fn stale_files(&self) -> Vec<StaleFile> {
+        Vec::new()
+    }
+
+    fn numa_memory(&self) -> HashMap<usize, u64> {
+        HashMap::new()
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -234,10 +267,48 @@ impl ProcessExt for Process {
         self.start_time
     }
 
+    fn started_at(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.start_time)
+    }
+
+    fn run_time(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.started_at())
+            .unwrap_or_default()
+    }
+
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn user_cpu_usage(&self) -> f32 {
+        self.user_cpu_usage
+    }
+
+    fn system_cpu_usage(&self) -> f32 {
+        self.system_cpu_usage
+    }
+
+    fn total_cpu_time(&self) -> Duration {
+        Duration::from_nanos(self.total_cpu_time)
+    }
+
+    fn thread_count(&self) -> usize {
+        self.thread_count
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        Some(Uid(self.uid))
+    }
+
+    fn cgroup(&self) -> &Path {
+        Path::new("")
+    }
+
+    fn is_kernel_thread(&self) -> bool {
+        false
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             read_bytes: self.read_bytes - self.old_read_bytes,
@@ -255,6 +326,7 @@ pub(crate) fn compute_cpu_usage(
     system_time: u64,
     user_time: u64,
     time_interval: Option<f64>,
+    timebase_to_ns: f64,
 ) {
     if let Some(time_interval) = time_interval {
         let total_existing_time = p.old_stime + p.old_utime;
@@ -262,23 +334,35 @@ pub(crate) fn compute_cpu_usage(
             let total_current_time = task_info.pti_total_system + task_info.pti_total_user;
 
             let total_time_diff = total_current_time.saturating_sub(total_existing_time);
+            let user_time_diff = task_info.pti_total_user.saturating_sub(p.old_utime);
+            let system_time_diff = task_info.pti_total_system.saturating_sub(p.old_stime);
             p.cpu_usage = (total_time_diff as f64 / time_interval * 100.) as f32;
+            p.user_cpu_usage = (user_time_diff as f64 / time_interval * 100.) as f32;
+            p.system_cpu_usage = (system_time_diff as f64 / time_interval * 100.) as f32;
         } else {
             p.cpu_usage = 0.;
+            p.user_cpu_usage = 0.;
+            p.system_cpu_usage = 0.;
         }
         p.old_stime = task_info.pti_total_system;
         p.old_utime = task_info.pti_total_user;
     } else {
         // This is the "backup way" of CPU computation.
         let time = unsafe { libc::mach_absolute_time() };
-        let task_time =
-            user_time + system_time + task_info.pti_total_user + task_info.pti_total_system;
+        let user_task_time = user_time + task_info.pti_total_user;
+        let task_time = user_task_time + system_time + task_info.pti_total_system;
 
         let system_time_delta = if task_time < p.old_utime {
             task_time
         } else {
             task_time - p.old_utime
         };
+        let user_time_delta = if user_task_time < p.old_user_time {
+            user_task_time
+        } else {
+            user_task_time - p.old_user_time
+        }
+        .min(system_time_delta);
         let time_delta = if time < p.old_stime {
             time
         } else {
@@ -286,12 +370,21 @@ pub(crate) fn compute_cpu_usage(
         };
         p.old_utime = task_time;
         p.old_stime = time;
-        p.cpu_usage = if time_delta == 0 {
-            0f32
-        } else {
-            (system_time_delta as f64 * 100f64 / time_delta as f64) as f32
+        p.old_user_time = user_task_time;
+        let usage = |delta: u64| {
+            if time_delta == 0 {
+                0f32
+            } else {
+                (delta as f64 * 100f64 / time_delta as f64) as f32
+            }
         };
+        p.cpu_usage = usage(system_time_delta);
+        p.user_cpu_usage = usage(user_time_delta);
+        p.system_cpu_usage = usage(system_time_delta - user_time_delta);
     }
+    // These times are in mach absolute time units, which aren't nanoseconds on all targets.
+    p.total_cpu_time =
+        ((task_info.pti_total_user + task_info.pti_total_system) as f64 * timebase_to_ns) as u64;
     p.updated = true;
 }
 
@@ -332,6 +425,8 @@ pub(crate) fn update_process(
     pid: Pid,
     mut size: size_t,
     time_interval: Option<f64>,
+    timebase_to_ns: f64,
+    refresh_kind: ProcessRefreshKind,
 ) -> Result<Option<Process>, ()> {
     let mut proc_args = Vec::with_capacity(size as usize);
 
@@ -343,6 +438,7 @@ pub(crate) fn update_process(
                 return Ok(None);
             }
             let task_info = get_task_info(pid);
+            p.thread_count = task_info.pti_threadnum as usize;
             let mut thread_info = mem::zeroed::<libc::proc_threadinfo>();
             let (user_time, system_time, thread_status) = if libc::proc_pidinfo(
                 pid,
@@ -361,11 +457,26 @@ pub(crate) fn update_process(
                 (0, 0, None)
             };
             p.status = thread_status;
-            compute_cpu_usage(p, task_info, system_time, user_time, time_interval);
-
-            p.memory = task_info.pti_resident_size / 1_000;
-            p.virtual_memory = task_info.pti_virtual_size / 1_000;
-            update_proc_disk_activity(p);
+            if refresh_kind.cpu() {
+                compute_cpu_usage(
+                    p,
+                    task_info,
+                    system_time,
+                    user_time,
+                    time_interval,
+                    timebase_to_ns,
+                );
+            } else {
+                // The CPU usage computation is what marks the process as updated.
+                force_update(p);
+            }
+            if refresh_kind.memory() {
+                p.memory = task_info.pti_resident_size;
+                p.virtual_memory = task_info.pti_virtual_size;
+            }
+            if refresh_kind.disk_usage() {
+                update_proc_disk_activity(p);
+            }
             return Ok(None);
         }
 
@@ -563,13 +674,16 @@ pub(crate) fn update_process(
 
         let task_info = get_task_info(pid);
 
-        p.memory = task_info.pti_resident_size / 1_000;
-        p.virtual_memory = task_info.pti_virtual_size / 1_000;
+        p.memory = task_info.pti_resident_size;
+        p.virtual_memory = task_info.pti_virtual_size;
+        p.thread_count = task_info.pti_threadnum as usize;
 
         p.uid = info.pbi_uid;
         p.gid = info.pbi_gid;
         p.process_status = ProcessStatus::from(info.pbi_status);
-        update_proc_disk_activity(&mut p);
+        if refresh_kind.disk_usage() {
+            update_proc_disk_activity(&mut p);
+        }
         Ok(Some(p))
     }
 }
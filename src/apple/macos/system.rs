@@ -67,6 +67,11 @@ impl SystemTimeInfo {
         })
     }
 
+    /// Returns the ratio to convert mach absolute time units into nanoseconds.
+    pub(crate) fn timebase_to_ns(&self) -> f64 {
+        self.timebase_to_ns
+    }
+
     fn update_ticks(
         port: mach_port_t,
         cpu_load: &mut processor_cpu_load_info_t,
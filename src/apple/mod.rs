@@ -13,6 +13,7 @@ pub(crate) use self::ios as inner;
 #[cfg(any(target_os = "ios", feature = "apple-sandbox"))]
 pub(crate) mod app_store;
 
+pub(crate) mod cgroup;
 pub mod component;
 pub mod disk;
 mod ffi;
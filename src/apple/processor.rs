@@ -115,6 +115,10 @@ impl ProcessorExt for Processor {
     fn brand(&self) -> &str {
         &self.brand
     }
+
+    fn numa_node(&self) -> Option<usize> {
+        None
+    }
 }
 
 pub fn get_cpu_frequency() -> u64 {
@@ -9,7 +9,11 @@ use crate::sys::processor::*;
 #[cfg(target_os = "macos")]
 use core_foundation_sys::base::{kCFAllocatorDefault, CFRelease};
 
-use crate::{LoadAvg, Pid, ProcessorExt, RefreshKind, SystemExt, User};
+use crate::{
+    Cgroups, HardwareInfo, KernelVersion, LoadAvg, LoginRecord, MemoryInfo, NumaNode, OsRelease,
+    Pid, Pressure, ProcessRefreshKind, ProcessorExt, RefreshKind, Session, Swap, SystemExt,
+    SystemdUnit, User, Virtualization, VmStats,
+};
 
 #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
 use crate::ProcessExt;
@@ -17,6 +21,7 @@ use crate::ProcessExt;
 use std::cell::UnsafeCell;
 use std::collections::HashMap;
 use std::mem;
+use std::path::Path;
 use std::sync::Arc;
 
 #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
@@ -35,15 +40,18 @@ pub struct System {
     mem_available: u64,
     swap_total: u64,
     swap_free: u64,
+    memory_info: MemoryInfo,
+    vm_stats: VmStats,
     global_processor: Processor,
     processors: Vec<Processor>,
-    page_size_kb: u64,
+    page_size: u64,
     components: Vec<Component>,
     // Used to get CPU information, not supported on iOS, or inside the default macOS sandbox.
     #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
     connection: Option<ffi::io_connect_t>,
     disks: Vec<Disk>,
     networks: Networks,
+    cgroups: Cgroups,
     port: mach_port_t,
     users: Vec<User>,
     boot_time: u64,
@@ -94,6 +102,11 @@ impl System {
             self.process_list.remove(&pid);
         }
     }
+
+    /// Removes the process `pid` from the list and returns it.
+    pub(crate) fn take_process(&mut self, pid: Pid) -> Option<Process> {
+        self.process_list.remove(&pid)
+    }
 }
 
 fn boot_time() -> u64 {
@@ -134,14 +147,17 @@ impl SystemExt for System {
             mem_available: 0,
             swap_total: 0,
             swap_free: 0,
+            memory_info: MemoryInfo::default(),
+            vm_stats: VmStats::default(),
             global_processor,
             processors,
-            page_size_kb: unsafe { sysconf(_SC_PAGESIZE) as u64 / 1_000 },
+            page_size: unsafe { sysconf(_SC_PAGESIZE) as u64 },
             components: Vec::with_capacity(2),
             #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
             connection: get_io_service_connection(),
             disks: Vec::with_capacity(1),
             networks: Networks::new(),
+            cgroups: Cgroups::new(),
             port,
             users: Vec::new(),
             boot_time: boot_time(),
@@ -168,8 +184,8 @@ impl SystemExt for System {
                 &mut xs as *mut _ as *mut c_void,
                 &mut mib,
             ) {
-                self.swap_total = xs.xsu_total / 1_000;
-                self.swap_free = xs.xsu_avail / 1_000;
+                self.swap_total = xs.xsu_total;
+                self.swap_free = xs.xsu_avail;
             }
             // get ram info
             if self.mem_total < 1 {
@@ -180,7 +196,6 @@ impl SystemExt for System {
                     &mut self.mem_total as *mut u64 as *mut c_void,
                     &mut mib,
                 );
-                self.mem_total /= 1_000;
             }
             let mut count: u32 = libc::HOST_VM_INFO64_COUNT as _;
             let mut stat = mem::zeroed::<vm_statistics64>();
@@ -205,10 +220,15 @@ impl SystemExt for System {
                         + u64::from(stat.wire_count)
                         + u64::from(stat.speculative_count)
                         - u64::from(stat.purgeable_count))
-                        * self.page_size_kb;
-                self.mem_free = u64::from(stat.free_count) * self.page_size_kb;
+                        * self.page_size;
+                self.mem_free = u64::from(stat.free_count) * self.page_size;
             }
         }
+        self.memory_info.total = self.mem_total;
+        self.memory_info.free = self.mem_free;
+        self.memory_info.available = self.mem_available;
+        self.memory_info.swap_total = self.swap_total;
+        self.memory_info.swap_free = self.swap_free;
     }
 
     #[cfg(any(target_os = "ios", feature = "apple-sandbox"))]
@@ -253,10 +273,10 @@ impl SystemExt for System {
     }
 
     #[cfg(any(target_os = "ios", feature = "apple-sandbox"))]
-    fn refresh_processes(&mut self) {}
+    fn refresh_processes_specifics(&mut self, _refresh_kind: ProcessRefreshKind) {}
 
     #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
-    fn refresh_processes(&mut self) {
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
         use crate::utils::into_iter;
 
         let count = unsafe { libc::proc_listallpids(::std::ptr::null_mut(), 0) };
@@ -267,6 +287,7 @@ impl SystemExt for System {
             let arg_max = get_arg_max();
             let port = self.port;
             let time_interval = self.clock_info.as_mut().map(|c| c.get_time_interval(port));
+            let timebase_to_ns = self.clock_info.as_ref().map_or(1., |c| c.timebase_to_ns());
             let entries: Vec<Process> = {
                 let wrap = &Wrap(UnsafeCell::new(&mut self.process_list));
 
@@ -275,7 +296,14 @@ impl SystemExt for System {
 
                 into_iter(pids)
                     .flat_map(|pid| {
-                        match update_process(wrap, pid, arg_max as size_t, time_interval) {
+                        match update_process(
+                            wrap,
+                            pid,
+                            arg_max as size_t,
+                            time_interval,
+                            timebase_to_ns,
+                            refresh_kind,
+                        ) {
                             Ok(x) => x,
                             _ => None,
                         }
@@ -290,18 +318,26 @@ impl SystemExt for System {
     }
 
     #[cfg(any(target_os = "ios", feature = "apple-sandbox"))]
-    fn refresh_process(&mut self, _: Pid) -> bool {
+    fn refresh_process_specifics(&mut self, _: Pid, _refresh_kind: ProcessRefreshKind) -> bool {
         false
     }
 
     #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
-    fn refresh_process(&mut self, pid: Pid) -> bool {
+    fn refresh_process_specifics(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
         let arg_max = get_arg_max();
         let port = self.port;
         let time_interval = self.clock_info.as_mut().map(|c| c.get_time_interval(port));
+        let timebase_to_ns = self.clock_info.as_ref().map_or(1., |c| c.timebase_to_ns());
         match {
             let wrap = Wrap(UnsafeCell::new(&mut self.process_list));
-            update_process(&wrap, pid, arg_max as size_t, time_interval)
+            update_process(
+                &wrap,
+                pid,
+                arg_max as size_t,
+                time_interval,
+                timebase_to_ns,
+                refresh_kind,
+            )
         } {
             Ok(Some(p)) => {
                 self.process_list.insert(p.pid(), p);
@@ -312,6 +348,52 @@ impl SystemExt for System {
         }
     }
 
+    #[cfg(any(target_os = "ios", feature = "apple-sandbox"))]
+    fn refresh_processes_list_specifics(
+        &mut self,
+        _pids: &[Pid],
+        _refresh_kind: ProcessRefreshKind,
+    ) {
+    }
+
+    #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
+    fn refresh_processes_list_specifics(&mut self, pids: &[Pid], refresh_kind: ProcessRefreshKind) {
+        let arg_max = get_arg_max();
+        let port = self.port;
+        // All processes share the same time interval.
+        let time_interval = self.clock_info.as_mut().map(|c| c.get_time_interval(port));
+        let timebase_to_ns = self.clock_info.as_ref().map_or(1., |c| c.timebase_to_ns());
+        for &pid in pids {
+            // `update_process` doesn't fail for already known processes so we need to check
+            // ourselves whether they are still alive.
+            if unsafe { libc::kill(pid, 0) } != 0
+                && std::io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH)
+            {
+                self.process_list.remove(&pid);
+                continue;
+            }
+            match {
+                let wrap = Wrap(UnsafeCell::new(&mut self.process_list));
+                update_process(
+                    &wrap,
+                    pid,
+                    arg_max as size_t,
+                    time_interval,
+                    timebase_to_ns,
+                    refresh_kind,
+                )
+            } {
+                Ok(Some(p)) => {
+                    self.process_list.insert(p.pid(), p);
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    self.process_list.remove(&pid);
+                }
+            }
+        }
+    }
+
     #[cfg(target_os = "ios")]
     fn refresh_disks_list(&mut self) {}
 
@@ -371,6 +453,14 @@ impl SystemExt for System {
         &mut self.networks
     }
 
+    fn cgroups(&self) -> &Cgroups {
+        &self.cgroups
+    }
+
+    fn cgroups_mut(&mut self) -> &mut Cgroups {
+        &mut self.cgroups
+    }
+
     fn total_memory(&self) -> u64 {
         self.mem_total
     }
@@ -400,6 +490,30 @@ impl SystemExt for System {
         self.swap_total - self.swap_free
     }
 
+    fn memory_info(&self) -> &MemoryInfo {
+        &self.memory_info
+    }
+
+    fn vm_stats(&self) -> &VmStats {
+        &self.vm_stats
+    }
+
+    fn swaps(&self) -> Vec<Swap> {
+        Vec::new()
+    }
+
+    fn numa_nodes(&self) -> Vec<NumaNode> {
+        Vec::new()
+    }
+
+    fn virtualization(&self) -> Virtualization {
+        Virtualization::None
+    }
+
+    fn hardware_info(&self) -> HardwareInfo {
+        HardwareInfo::default()
+    }
+
     fn components(&self) -> &[Component] {
         &self.components
     }
@@ -434,10 +548,30 @@ impl SystemExt for System {
         }
     }
 
+    fn pressure(&self) -> Pressure {
+        Pressure::default()
+    }
+
+    fn cgroup_pressure(&self, _cgroup: &Path) -> Pressure {
+        Pressure::default()
+    }
+
     fn users(&self) -> &[User] {
         &self.users
     }
 
+    fn sessions(&self) -> Vec<Session> {
+        Vec::new()
+    }
+
+    fn login_history(&self) -> Vec<LoginRecord> {
+        Vec::new()
+    }
+
+    fn systemd_units(&self) -> Vec<SystemdUnit> {
+        Vec::new()
+    }
+
     fn boot_time(&self) -> u64 {
         self.boot_time
     }
@@ -496,6 +630,10 @@ impl SystemExt for System {
         get_system_info(libc::KERN_OSRELEASE, None)
     }
 
+    fn parsed_kernel_version(&self) -> Option<KernelVersion> {
+        crate::utils::uname_kernel_version()
+    }
+
     fn os_version(&self) -> Option<String> {
         unsafe {
             // get the size for the buffer first
@@ -527,6 +665,10 @@ impl SystemExt for System {
             }
         }
     }
+
+    fn os_release(&self) -> Option<OsRelease> {
+        None
+    }
 }
 
 impl Default for System {
@@ -0,0 +1,296 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::Pid;
+
+use std::collections::{hash_map, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Value which is only increasing, keeping its previous value to compute the difference between
+/// two refreshes.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Counter {
+    pub(crate) old: u64,
+    /// `None` until the first update.
+    pub(crate) new: Option<u64>,
+}
+
+impl Counter {
+    fn delta(&self) -> u64 {
+        self.total().saturating_sub(self.old)
+    }
+
+    fn total(&self) -> u64 {
+        self.new.unwrap_or(0)
+    }
+}
+
+/// Control groups of the system (only available on linux).
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_cgroups_list();
+/// for (path, cgroup) in s.cgroups() {
+///     println!("{}: {} bytes, {:?}", path.display(), cgroup.memory(), cgroup.total_cpu_time());
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Cgroups {
+    cgroups: HashMap<PathBuf, Cgroup>,
+}
+
+impl Cgroups {
+    pub(crate) fn new() -> Self {
+        Cgroups::default()
+    }
+
+    /// Returns an iterator over the control groups, the key being the path of the control group
+    /// from the root of the hierarchy (`/system.slice/cron.service` for example).
+    pub fn iter(&self) -> CgroupsIter<'_> {
+        CgroupsIter {
+            inner: self.cgroups.iter(),
+        }
+    }
+
+    /// Returns the `path` control group if it was found by the last call to
+    /// [`Cgroups::refresh_cgroups_list`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    /// use std::path::Path;
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_cgroups_list();
+    /// if let Some(cgroup) = s.cgroups().get(Path::new("/system.slice")) {
+    ///     println!("{} processes", cgroup.pids().len());
+    /// }
+    /// ```
+    pub fn get(&self, path: &Path) -> Option<&Cgroup> {
+        self.cgroups.get(path)
+    }
+
+    /// Walks the cgroup hierarchies to add the new control groups and remove the ones which don't
+    /// exist anymore, then refreshes them.
+    pub fn refresh_cgroups_list(&mut self) {
+        crate::sys::cgroup::refresh_cgroups_list(&mut self.cgroups);
+    }
+
+    /// Refreshes the statistics of the known control groups.
+    pub fn refresh(&mut self) {
+        crate::sys::cgroup::refresh_cgroups(&mut self.cgroups);
+    }
+}
+
+/// Iterator over the control groups.
+///
+/// It is returned by [`Cgroups::iter`].
+pub struct CgroupsIter<'a> {
+    inner: hash_map::Iter<'a, PathBuf, Cgroup>,
+}
+
+impl<'a> Iterator for CgroupsIter<'a> {
+    type Item = (&'a PathBuf, &'a Cgroup);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<'a> IntoIterator for &'a Cgroups {
+    type Item = (&'a PathBuf, &'a Cgroup);
+    type IntoIter = CgroupsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Statistics of a control group.
+///
+/// Methods without the `total_` prefix return the difference since the last refresh. Values which
+/// aren't available (because the controller isn't enabled for example) are `0`.
+#[derive(Debug, Default)]
+pub struct Cgroup {
+    pub(crate) pids: Vec<Pid>,
+    /// In microseconds.
+    pub(crate) cpu_usage: Counter,
+    pub(crate) periods: Counter,
+    pub(crate) throttled_periods: Counter,
+    /// In microseconds.
+    pub(crate) throttled_time: Counter,
+    pub(crate) memory: u64,
+    pub(crate) memory_limit: Option<u64>,
+    pub(crate) ooms: Counter,
+    pub(crate) oom_kills: Counter,
+    pub(crate) io: HashMap<String, CgroupIo>,
+    pub(crate) tasks: u64,
+    pub(crate) tasks_limit: Option<u64>,
+}
+
+impl Cgroup {
+    /// Returns the processes which are directly in this control group (not in its children).
+    pub fn pids(&self) -> &[Pid] {
+        &self.pids
+    }
+
+    /// Returns the CPU time used since the last refresh.
+    pub fn cpu_time(&self) -> Duration {
+        Duration::from_micros(self.cpu_usage.delta())
+    }
+
+    /// Returns the total CPU time used.
+    pub fn total_cpu_time(&self) -> Duration {
+        Duration::from_micros(self.cpu_usage.total())
+    }
+
+    /// Returns the number of CPU bandwidth periods elapsed since the last refresh.
+    pub fn periods(&self) -> u64 {
+        self.periods.delta()
+    }
+
+    /// Returns the total number of CPU bandwidth periods elapsed.
+    pub fn total_periods(&self) -> u64 {
+        self.periods.total()
+    }
+
+    /// Returns the number of periods during which the control group has been throttled since the
+    /// last refresh.
+    pub fn throttled_periods(&self) -> u64 {
+        self.throttled_periods.delta()
+    }
+
+    /// Returns the total number of periods during which the control group has been throttled.
+    pub fn total_throttled_periods(&self) -> u64 {
+        self.throttled_periods.total()
+    }
+
+    /// Returns the time the control group has been throttled since the last refresh.
+    pub fn throttled_time(&self) -> Duration {
+        Duration::from_micros(self.throttled_time.delta())
+    }
+
+    /// Returns the total time the control group has been throttled.
+    pub fn total_throttled_time(&self) -> Duration {
+        Duration::from_micros(self.throttled_time.total())
+    }
+
+    /// Returns the memory usage (in bytes).
+    pub fn memory(&self) -> u64 {
+        self.memory
+    }
+
+    /// Returns the memory limit (in bytes), `None` if there is none.
+    pub fn memory_limit(&self) -> Option<u64> {
+        self.memory_limit
+    }
+
+    /// Returns the number of times the control group reached its memory limit and the OOM killer
+    /// was invoked since the last refresh. It is always `0` with cgroup v1.
+    pub fn ooms(&self) -> u64 {
+        self.ooms.delta()
+    }
+
+    /// Returns the total number of times the OOM killer was invoked. It is always `0` with
+    /// cgroup v1.
+    pub fn total_ooms(&self) -> u64 {
+        self.ooms.total()
+    }
+
+    /// Returns the number of processes killed by the OOM killer since the last refresh.
+    pub fn oom_kills(&self) -> u64 {
+        self.oom_kills.delta()
+    }
+
+    /// Returns the total number of processes killed by the OOM killer.
+    pub fn total_oom_kills(&self) -> u64 {
+        self.oom_kills.total()
+    }
+
+    /// Returns the I/O statistics per device, the key being the `major:minor` device number.
+    pub fn io(&self) -> &HashMap<String, CgroupIo> {
+        &self.io
+    }
+
+    /// Returns the number of tasks (processes and threads) in the control group and its children.
+    pub fn tasks(&self) -> u64 {
+        self.tasks
+    }
+
+    /// Returns the maximum number of tasks, `None` if there is no limit.
+    pub fn tasks_limit(&self) -> Option<u64> {
+        self.tasks_limit
+    }
+}
+
+/// I/O statistics of a control group on a device.
+#[derive(Debug, Default)]
+pub struct CgroupIo {
+    pub(crate) read_bytes: Counter,
+    pub(crate) written_bytes: Counter,
+    pub(crate) read_operations: Counter,
+    pub(crate) write_operations: Counter,
+}
+
+impl CgroupIo {
+    /// Returns the number of bytes read since the last refresh.
+    pub fn read_bytes(&self) -> u64 {
+        self.read_bytes.delta()
+    }
+
+    /// Returns the total number of bytes read.
+    pub fn total_read_bytes(&self) -> u64 {
+        self.read_bytes.total()
+    }
+
+    /// Returns the number of bytes written since the last refresh.
+    pub fn written_bytes(&self) -> u64 {
+        self.written_bytes.delta()
+    }
+
+    /// Returns the total number of bytes written.
+    pub fn total_written_bytes(&self) -> u64 {
+        self.written_bytes.total()
+    }
+
+    /// Returns the number of read operations since the last refresh.
+    pub fn read_operations(&self) -> u64 {
+        self.read_operations.delta()
+    }
+
+    /// Returns the total number of read operations.
+    pub fn total_read_operations(&self) -> u64 {
+        self.read_operations.total()
+    }
+
+    /// Returns the number of write operations since the last refresh.
+    pub fn write_operations(&self) -> u64 {
+        self.write_operations.delta()
+    }
+
+    /// Returns the total number of write operations.
+    pub fn total_write_operations(&self) -> u64 {
+        self.write_operations.total()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Counter;
+
+    #[test]
+    fn check_counter() {
+        let counter = Counter {
+            old: 10,
+            new: Some(25),
+        };
+        assert_eq!((counter.delta(), counter.total()), (15, 25));
+        // A counter going backward (after a reset) shouldn't underflow.
+        let counter = Counter {
+            old: 25,
+            new: Some(5),
+        };
+        assert_eq!((counter.delta(), counter.total()), (0, 5));
+    }
+}
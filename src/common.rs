@@ -1,6 +1,10 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use crate::{NetworkData, Networks, NetworksExt, UserExt};
+use crate::{NetworkData, Networks, NetworksExt, ProcessGroup, UserExt};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
 
 /// Trait to have a common fallback for the [`Pid`][crate::Pid] type.
 pub trait AsU32 {
@@ -33,13 +37,13 @@ cfg_if::cfg_if! {
 }
 
 macro_rules! impl_get_set {
-    ($name:ident, $with:ident, $without:ident) => {
+    ($ty_name:ident, $name:ident, $with:ident, $without:ident) => {
         #[doc = concat!("Returns the value of the \"", stringify!($name), "\" refresh kind.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::new();
+let r = ", stringify!($ty_name), "::new();
 assert_eq!(r.", stringify!($name), "(), false);
 
 let r = r.with_", stringify!($name), "();
@@ -55,15 +59,15 @@ assert_eq!(r.", stringify!($name), "(), false);
         #[doc = concat!("Sets the value of the \"", stringify!($name), "\" refresh kind to `true`.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::new();
+let r = ", stringify!($ty_name), "::new();
 assert_eq!(r.", stringify!($name), "(), false);
 
 let r = r.with_", stringify!($name), "();
 assert_eq!(r.", stringify!($name), "(), true);
 ```")]
-        pub fn $with(mut self) -> RefreshKind {
+        pub fn $with(mut self) -> Self {
             self.$name = true;
             self
         }
@@ -71,15 +75,15 @@ assert_eq!(r.", stringify!($name), "(), true);
         #[doc = concat!("Sets the value of the \"", stringify!($name), "\" refresh kind to `false`.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::everything();
+let r = ", stringify!($ty_name), "::everything();
 assert_eq!(r.", stringify!($name), "(), true);
 
 let r = r.without_", stringify!($name), "();
 assert_eq!(r.", stringify!($name), "(), false);
 ```")]
-        pub fn $without(mut self) -> RefreshKind {
+        pub fn $without(mut self) -> Self {
             self.$name = false;
             self
         }
@@ -171,20 +175,193 @@ impl RefreshKind {
         }
     }
 
-    impl_get_set!(networks, with_networks, without_networks);
-    impl_get_set!(networks_list, with_networks_list, without_networks_list);
-    impl_get_set!(processes, with_processes, without_processes);
-    impl_get_set!(disks, with_disks, without_disks);
-    impl_get_set!(disks_list, with_disks_list, without_disks_list);
-    impl_get_set!(memory, with_memory, without_memory);
-    impl_get_set!(cpu, with_cpu, without_cpu);
-    impl_get_set!(components, with_components, without_components);
+    impl_get_set!(RefreshKind, networks, with_networks, without_networks);
+    impl_get_set!(
+        RefreshKind,
+        networks_list,
+        with_networks_list,
+        without_networks_list
+    );
+    impl_get_set!(RefreshKind, processes, with_processes, without_processes);
+    impl_get_set!(RefreshKind, disks, with_disks, without_disks);
+    impl_get_set!(RefreshKind, disks_list, with_disks_list, without_disks_list);
+    impl_get_set!(RefreshKind, memory, with_memory, without_memory);
+    impl_get_set!(RefreshKind, cpu, with_cpu, without_cpu);
+    impl_get_set!(RefreshKind, components, with_components, without_components);
     impl_get_set!(
+        RefreshKind,
         components_list,
         with_components_list,
         without_components_list
     );
-    impl_get_set!(users_list, with_users_list, without_users_list);
+    impl_get_set!(RefreshKind, users_list, with_users_list, without_users_list);
+}
+
+/// Used to determine what you want to refresh specifically on [`Process`] type.
+///
+/// Information which doesn't change during the process' life (like the command line or the
+/// executable path) is only retrieved the first time it is asked for.
+///
+/// Unlike the other refreshes, `kernel_threads` doesn't select information to retrieve and is
+/// enabled by default, even with [`ProcessRefreshKind::new`]: when it is disabled with
+/// [`ProcessRefreshKind::without_kernel_threads`], kernel threads are not listed at all (and
+/// removed if they already were). It is only used on Linux.
+///
+/// ⚠️ Just like all other refresh types, ruling out a refresh doesn't assure you that
+/// the information won't be retrieved if the information is accessible without needing
+/// extra computation.
+///
+/// ```
+/// use sysinfo::{ProcessExt, ProcessRefreshKind, System, SystemExt};
+///
+/// let mut system = System::new();
+///
+/// // We only want to update the CPU and memory information.
+/// system.refresh_processes_specifics(ProcessRefreshKind::new().with_cpu().with_memory());
+///
+/// for (pid, process) in system.processes() {
+///     println!("[{}] {}% {} bytes", pid, process.cpu_usage(), process.memory());
+/// }
+/// ```
+///
+/// [`Process`]: crate::Process
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProcessRefreshKind {
+    cpu: bool,
+    memory: bool,
+    disk_usage: bool,
+    cmd: bool,
+    environ: bool,
+    exe: bool,
+    cwd: bool,
+    root: bool,
+    user: bool,
+    cgroup: bool,
+    threads: bool,
+    skip_kernel_threads: bool,
+}
+
+impl ProcessRefreshKind {
+    /// Creates a new `ProcessRefreshKind` with every refresh set to `false`, except
+    /// `kernel_threads`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::new();
+    ///
+    /// assert_eq!(r.cpu(), false);
+    /// assert_eq!(r.memory(), false);
+    /// assert_eq!(r.disk_usage(), false);
+    /// assert_eq!(r.cmd(), false);
+    /// assert_eq!(r.environ(), false);
+    /// assert_eq!(r.exe(), false);
+    /// assert_eq!(r.cwd(), false);
+    /// assert_eq!(r.root(), false);
+    /// assert_eq!(r.user(), false);
+    /// assert_eq!(r.cgroup(), false);
+    /// assert_eq!(r.threads(), false);
+    /// assert_eq!(r.kernel_threads(), true);
+    /// ```
+    pub fn new() -> ProcessRefreshKind {
+        ProcessRefreshKind::default()
+    }
+
+    /// Creates a new `ProcessRefreshKind` with every refresh set to `true`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::everything();
+    ///
+    /// assert_eq!(r.cpu(), true);
+    /// assert_eq!(r.memory(), true);
+    /// assert_eq!(r.disk_usage(), true);
+    /// assert_eq!(r.cmd(), true);
+    /// assert_eq!(r.environ(), true);
+    /// assert_eq!(r.exe(), true);
+    /// assert_eq!(r.cwd(), true);
+    /// assert_eq!(r.root(), true);
+    /// assert_eq!(r.user(), true);
+    /// assert_eq!(r.cgroup(), true);
+    /// assert_eq!(r.threads(), true);
+    /// assert_eq!(r.kernel_threads(), true);
+    /// ```
+    pub fn everything() -> ProcessRefreshKind {
+        ProcessRefreshKind {
+            cpu: true,
+            memory: true,
+            disk_usage: true,
+            cmd: true,
+            environ: true,
+            exe: true,
+            cwd: true,
+            root: true,
+            user: true,
+            cgroup: true,
+            threads: true,
+            skip_kernel_threads: false,
+        }
+    }
+
+    impl_get_set!(ProcessRefreshKind, cpu, with_cpu, without_cpu);
+    impl_get_set!(ProcessRefreshKind, memory, with_memory, without_memory);
+    impl_get_set!(
+        ProcessRefreshKind,
+        disk_usage,
+        with_disk_usage,
+        without_disk_usage
+    );
+    impl_get_set!(ProcessRefreshKind, cmd, with_cmd, without_cmd);
+    impl_get_set!(ProcessRefreshKind, environ, with_environ, without_environ);
+    impl_get_set!(ProcessRefreshKind, exe, with_exe, without_exe);
+    impl_get_set!(ProcessRefreshKind, cwd, with_cwd, without_cwd);
+    impl_get_set!(ProcessRefreshKind, root, with_root, without_root);
+    impl_get_set!(ProcessRefreshKind, user, with_user, without_user);
+    impl_get_set!(ProcessRefreshKind, cgroup, with_cgroup, without_cgroup);
+    impl_get_set!(ProcessRefreshKind, threads, with_threads, without_threads);
+
+    /// Returns `true` if kernel threads are listed, which is the default.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::new();
+    /// assert_eq!(r.kernel_threads(), true);
+    ///
+    /// let r = r.without_kernel_threads();
+    /// assert_eq!(r.kernel_threads(), false);
+    /// ```
+    pub fn kernel_threads(&self) -> bool {
+        !self.skip_kernel_threads
+    }
+
+    /// Lists kernel threads again after a call to
+    /// [`ProcessRefreshKind::without_kernel_threads`].
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::new().without_kernel_threads().with_kernel_threads();
+    /// assert_eq!(r.kernel_threads(), true);
+    /// ```
+    pub fn with_kernel_threads(mut self) -> Self {
+        self.skip_kernel_threads = false;
+        self
+    }
+
+    /// Stops listing kernel threads and removes the ones already listed.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::everything().without_kernel_threads();
+    /// assert_eq!(r.kernel_threads(), false);
+    /// ```
+    pub fn without_kernel_threads(mut self) -> Self {
+        self.skip_kernel_threads = true;
+        self
+    }
 }
 
 /// Iterator over network interfaces.
@@ -347,10 +524,424 @@ pub struct LoadAvg {
     pub fifteen: f64,
 }
 
+/// A counter of `/proc/vmstat`.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct VmCounter {
+    /// Value of the counter since boot.
+    pub total: u64,
+    /// Average increase per second between the last two refreshes. It is `0.` after the first
+    /// refresh.
+    pub per_second: f64,
+}
+
+/// Virtual memory statistics, returned by [`SystemExt::vm_stats`].
+///
+/// They come from `/proc/vmstat` and are updated by [`SystemExt::refresh_memory`]. They are only
+/// available on linux, otherwise all the counters are `0`.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_memory();
+/// std::thread::sleep(std::time::Duration::from_secs(1));
+/// s.refresh_memory();
+/// let stats = s.vm_stats();
+/// if stats.pswpin.per_second + stats.pswpout.per_second > 100. {
+///     println!("the system is swapping a lot");
+/// }
+/// ```
+///
+/// [`SystemExt::vm_stats`]: crate::SystemExt::vm_stats
+/// [`SystemExt::refresh_memory`]: crate::SystemExt::refresh_memory
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct VmStats {
+    /// KiB paged in from the disk.
+    pub pgpgin: VmCounter,
+    /// KiB paged out to the disk.
+    pub pgpgout: VmCounter,
+    /// Pages swapped in.
+    pub pswpin: VmCounter,
+    /// Pages swapped out.
+    pub pswpout: VmCounter,
+    /// Page faults.
+    pub pgfault: VmCounter,
+    /// Major page faults (which required I/O).
+    pub pgmajfault: VmCounter,
+    /// Pages scanned to be reclaimed, by `kswapd`, `khugepaged` and direct reclaim.
+    pub pgscan: VmCounter,
+    /// Pages reclaimed, by `kswapd`, `khugepaged` and direct reclaim.
+    pub pgsteal: VmCounter,
+    /// Processes killed by the OOM killer.
+    pub oom_kill: VmCounter,
+    /// Transparent huge pages allocated on page fault.
+    pub thp_fault_alloc: VmCounter,
+    /// Page faults which failed to allocate a transparent huge page.
+    pub thp_fault_fallback: VmCounter,
+    /// Transparent huge pages allocated by `khugepaged` collapsing pages.
+    pub thp_collapse_alloc: VmCounter,
+    /// Transparent huge pages split into normal pages.
+    pub thp_split_page: VmCounter,
+    /// The other counters.
+    pub others: HashMap<String, VmCounter>,
+}
+
+/// A NUMA node, returned by [`SystemExt::numa_nodes`].
+///
+/// ```no_run
+/// use sysinfo::{Bytes, System, SystemExt};
+///
+/// let s = System::new();
+/// for node in s.numa_nodes() {
+///     println!(
+///         "node {}: CPUs {:?}, {} free, {} remote allocations",
+///         node.id,
+///         node.cpus,
+///         Bytes(node.free_memory),
+///         node.numa_miss,
+///     );
+/// }
+/// ```
+///
+/// [`SystemExt::numa_nodes`]: crate::SystemExt::numa_nodes
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct NumaNode {
+    /// Identifier of the node.
+    pub id: usize,
+    /// Identifiers of the CPUs of the node, as returned by
+    /// [`ProcessorExt::numa_node`][crate::ProcessorExt::numa_node].
+    pub cpus: Vec<usize>,
+    /// Memory (in bytes) of the node.
+    pub total_memory: u64,
+    /// Free memory (in bytes) of the node.
+    pub free_memory: u64,
+    /// Used memory (in bytes) of the node.
+    pub used_memory: u64,
+    /// Relative distances to each node, in the same order as the nodes are returned. The
+    /// distance to the node itself is usually `10`.
+    pub distances: Vec<u32>,
+    /// Pages allocated on this node as intended.
+    pub numa_hit: u64,
+    /// Pages allocated on this node while they were intended for another one.
+    pub numa_miss: u64,
+    /// Pages intended for this node but allocated on another one.
+    pub numa_foreign: u64,
+    /// Pages allocated on this node by an interleave policy as intended.
+    pub interleave_hit: u64,
+    /// Pages allocated on this node while the process was running on it.
+    pub local_node: u64,
+    /// Pages allocated on this node while the process was running on another one.
+    pub other_node: u64,
+}
+
+/// Kind of a swap device.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SwapType {
+    /// A block device (including zram devices).
+    Partition,
+    /// A file.
+    File,
+    /// Unknown type.
+    Unknown,
+}
+
+/// Statistics of a zram device, read from `/sys/block/zramN/mm_stat`.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct ZramStats {
+    /// Size (in bytes) of the data stored in the device, before compression.
+    pub original_size: u64,
+    /// Size (in bytes) of the data stored in the device, after compression.
+    pub compressed_size: u64,
+    /// Memory (in bytes) used by the device, including its metadata and fragmentation.
+    pub memory_used: u64,
+}
+
+impl ZramStats {
+    /// Returns the ratio between the original and the compressed size of the stored data, or
+    /// `0.` if the device is empty.
+    pub fn compression_ratio(&self) -> f64 {
+        if self.compressed_size == 0 {
+            0.
+        } else {
+            self.original_size as f64 / self.compressed_size as f64
+        }
+    }
+}
+
+/// A swap device, returned by [`SystemExt::swaps`].
+///
+/// ```no_run
+/// use sysinfo::{Bytes, System, SystemExt};
+///
+/// let s = System::new();
+/// for swap in s.swaps() {
+///     print!("{}: {} used out of {}", swap.path.display(), Bytes(swap.used), Bytes(swap.size));
+///     if let Some(zram) = swap.zram {
+///         print!(" (compression ratio: {:.2})", zram.compression_ratio());
+///     }
+///     println!();
+/// }
+/// ```
+///
+/// [`SystemExt::swaps`]: crate::SystemExt::swaps
+#[derive(Debug, PartialEq, Clone)]
+pub struct Swap {
+    /// Path of the device or of the file.
+    pub path: PathBuf,
+    /// Kind of the swap.
+    pub type_: SwapType,
+    /// Size (in bytes).
+    pub size: u64,
+    /// Used space (in bytes).
+    pub used: u64,
+    /// Priority. Devices with a higher priority are used first.
+    pub priority: i32,
+    /// Statistics of the device if it is a zram device.
+    pub zram: Option<ZramStats>,
+}
+
+/// Virtual machine or container the system is running in, as returned by
+/// [`SystemExt::virtualization`][crate::SystemExt::virtualization].
+///
+/// When running in a container inside a virtual machine, the container is returned.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// let virtualization = s.virtualization();
+/// if virtualization.is_container() {
+///     println!("running in a {:?} container", virtualization);
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Virtualization {
+    /// Not virtualized, or the virtualization couldn't be detected.
+    None,
+    /// KVM virtual machine.
+    Kvm,
+    /// QEMU virtual machine (without KVM).
+    Qemu,
+    /// VMware virtual machine.
+    VMware,
+    /// Xen virtual machine.
+    Xen,
+    /// Hyper-V virtual machine.
+    HyperV,
+    /// VirtualBox virtual machine.
+    VirtualBox,
+    /// Windows Subsystem for Linux.
+    Wsl,
+    /// Virtual machine whose hypervisor couldn't be identified.
+    OtherVm,
+    /// Docker container.
+    Docker,
+    /// Podman container.
+    Podman,
+    /// LXC container.
+    Lxc,
+    /// systemd-nspawn container.
+    SystemdNspawn,
+    /// Container of a Kubernetes pod, whatever its runtime is.
+    Kubernetes,
+    /// Container whose runtime couldn't be identified.
+    OtherContainer,
+}
+
+impl Virtualization {
+    /// Returns `true` if it is a virtual machine (including WSL).
+    pub fn is_vm(&self) -> bool {
+        matches!(
+            self,
+            Virtualization::Kvm
+                | Virtualization::Qemu
+                | Virtualization::VMware
+                | Virtualization::Xen
+                | Virtualization::HyperV
+                | Virtualization::VirtualBox
+                | Virtualization::Wsl
+                | Virtualization::OtherVm
+        )
+    }
+
+    /// Returns `true` if it is a container.
+    pub fn is_container(&self) -> bool {
+        !self.is_vm() && *self != Virtualization::None
+    }
+}
+
+/// Identity of the machine, read from its DMI/SMBIOS tables, as returned by
+/// [`SystemExt::hardware_info`][crate::SystemExt::hardware_info].
+///
+/// Fields are empty if they're not available, which is the case on machines without DMI (most
+/// ARM boards for example). `product_serial` and `product_uuid` are only readable by root, so
+/// they're `None` otherwise.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// let hardware = s.hardware_info();
+/// println!("{} {} ({:?})", hardware.system_vendor, hardware.product_name, hardware.product_serial);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct HardwareInfo {
+    /// Manufacturer of the machine.
+    pub system_vendor: String,
+    /// Model of the machine.
+    pub product_name: String,
+    /// Version of the model.
+    pub product_version: String,
+    /// Serial number of the machine, only readable by root.
+    pub product_serial: Option<String>,
+    /// UUID of the machine, only readable by root.
+    pub product_uuid: Option<String>,
+    /// Manufacturer of the motherboard.
+    pub board_vendor: String,
+    /// Model of the motherboard.
+    pub board_name: String,
+    /// Vendor of the BIOS (or UEFI firmware).
+    pub bios_vendor: String,
+    /// Version of the BIOS.
+    pub bios_version: String,
+    /// Release date of the BIOS, usually formatted as `MM/DD/YYYY`.
+    pub bios_date: String,
+    /// Kind of enclosure of the machine, `None` if it isn't available.
+    pub chassis_type: Option<ChassisType>,
+}
+
+/// Identification of the operating system, read from the `os-release` file, as returned by
+/// [`SystemExt::os_release`][crate::SystemExt::os_release].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(os) = s.os_release() {
+///     if os.id == "debian" || os.id_like.iter().any(|id| id == "debian") {
+///         println!("{} uses apt", os.pretty_name);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct OsRelease {
+    /// Name of the operating system (`NAME`), `Linux` if it isn't set.
+    pub name: String,
+    /// Lower-case identifier of the operating system (`ID`), `linux` if it isn't set.
+    pub id: String,
+    /// Identifiers of the operating systems this one is derived from (`ID_LIKE`), the closest one
+    /// first.
+    pub id_like: Vec<String>,
+    /// Name of the operating system suitable for presentation to the user (`PRETTY_NAME`),
+    /// `Linux` if it isn't set.
+    pub pretty_name: String,
+    /// Version of the operating system (`VERSION`).
+    pub version: Option<String>,
+    /// Lower-case version identifier (`VERSION_ID`).
+    pub version_id: Option<String>,
+    /// Lower-case release code name (`VERSION_CODENAME`).
+    pub version_codename: Option<String>,
+    /// Build identifier of the system image (`BUILD_ID`).
+    pub build_id: Option<String>,
+    /// Lower-case identifier of the variant, like `server` or `workstation` (`VARIANT_ID`).
+    pub variant_id: Option<String>,
+    /// Homepage of the operating system (`HOME_URL`).
+    pub home_url: Option<String>,
+    /// The other keys.
+    pub others: HashMap<String, String>,
+}
+
+/// Kind of enclosure of a machine, grouping the SMBIOS chassis types.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ChassisType {
+    /// Desktop, tower, all-in-one, mini PC...
+    Desktop,
+    /// Laptop, notebook, convertible...
+    Laptop,
+    /// Tablet or detachable.
+    Tablet,
+    /// Server, rack mount chassis, blade...
+    Server,
+    /// Embedded PC or IoT gateway.
+    Embedded,
+    /// Any other SMBIOS chassis type, whose code is given.
+    Other(u8),
+}
+
+impl From<u8> for ChassisType {
+    /// Converts a SMBIOS chassis type code.
+    fn from(code: u8) -> ChassisType {
+        match code {
+            3 | 4 | 5 | 6 | 7 | 13 | 15 | 16 | 24 | 35 => ChassisType::Desktop,
+            8 | 9 | 10 | 14 | 31 => ChassisType::Laptop,
+            11 | 30 | 32 => ChassisType::Tablet,
+            17 | 23 | 25 | 28 | 29 => ChassisType::Server,
+            33 | 34 => ChassisType::Embedded,
+            code => ChassisType::Other(code),
+        }
+    }
+}
+
+/// Share of time during which tasks were stalled on a resource.
+///
+/// It is one line of a Pressure Stall Information file (like `/proc/pressure/cpu`).
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct PressureStats {
+    /// Share of time (in %) stalled within the last ten seconds.
+    pub avg10: f64,
+    /// Share of time (in %) stalled within the last minute.
+    pub avg60: f64,
+    /// Share of time (in %) stalled within the last five minutes.
+    pub avg300: f64,
+    /// Total stall time (in microseconds).
+    pub total: u64,
+}
+
+/// Pressure of a resource.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct ResourcePressure {
+    /// Stalls during which at least some tasks were waiting for the resource. It is `None` for
+    /// the IRQ pressure.
+    pub some: Option<PressureStats>,
+    /// Stalls during which all non-idle tasks were waiting for the resource at the same time. It
+    /// is `None` for the CPU pressure on kernels older than 5.13.
+    pub full: Option<PressureStats>,
+}
+
+/// Pressure Stall Information, returned by [`SystemExt::pressure`] and
+/// [`SystemExt::cgroup_pressure`].
+///
+/// It tells how much time tasks were waiting for the CPU, the memory or I/O, which is a better
+/// saturation signal than the load average. It is only available on linux 4.20 and newer,
+/// otherwise all the fields are `None`.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(some) = s.pressure().cpu.and_then(|cpu| cpu.some) {
+///     println!("tasks waited for the CPU {}% of the last ten seconds", some.avg10);
+/// }
+/// ```
+///
+/// [`SystemExt::pressure`]: crate::SystemExt::pressure
+/// [`SystemExt::cgroup_pressure`]: crate::SystemExt::cgroup_pressure
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Pressure {
+    /// CPU pressure.
+    pub cpu: Option<ResourcePressure>,
+    /// Memory pressure.
+    pub memory: Option<ResourcePressure>,
+    /// I/O pressure.
+    pub io: Option<ResourcePressure>,
+    /// IRQ pressure (linux 6.1 and newer).
+    pub irq: Option<ResourcePressure>,
+}
+
 macro_rules! xid {
     ($(#[$outer:meta])+ $name:ident, $type:ty) => {
         $(#[$outer])+
-        #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+        #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
         pub struct $name(pub(crate) $type);
 
         impl std::ops::Deref for $name {
@@ -427,6 +1018,44 @@ impl UserExt for User {
     }
 }
 
+/// A user logged in, as returned by [`SystemExt::sessions`][crate::SystemExt::sessions].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for session in s.sessions() {
+///     let leader = s.process(session.pid).map(|p| p.name()).unwrap_or("?");
+///     println!("{} on {} from {:?} ({})", session.user, session.tty, session.host, leader);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    /// Name of the user.
+    pub user: String,
+    /// Terminal of the session, without the `/dev/` prefix (`pts/0` or `tty1` for example).
+    pub tty: String,
+    /// Host the user is connected from, `None` for local sessions.
+    pub host: Option<String>,
+    /// Time of the login (in seconds since UNIX epoch).
+    pub login_time: u64,
+    /// PID of the session leader (usually the login shell or `sshd`), which can be used with
+    /// [`SystemExt::process`][crate::SystemExt::process].
+    pub pid: Pid,
+}
+
+/// A past or current session, as returned by
+/// [`SystemExt::login_history`][crate::SystemExt::login_history].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginRecord {
+    /// The session.
+    pub session: Session,
+    /// Time of the logout (in seconds since UNIX epoch), `None` if the user is still logged in.
+    /// When the system was shut down or crashed before the logout, it is the time of the shutdown
+    /// or of the next boot.
+    pub logout_time: Option<u64>,
+}
+
 /// Type containing read and written bytes.
 ///
 /// It is returned by [`ProcessExt::disk_usage`][crate::ProcessExt::disk_usage].
@@ -461,6 +1090,371 @@ pub struct DiskUsage {
     pub read_bytes: u64,
 }
 
+/// Reason why a file used by a process is stale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StaleFileReason {
+    /// The file has been deleted.
+    Deleted,
+    /// The file has been replaced by another one (it has a different inode), like when a package
+    /// is upgraded.
+    Replaced,
+}
+
+/// File used by a process which doesn't match the one on disk anymore.
+///
+/// It is returned by [`ProcessExt::stale_files`][crate::ProcessExt::stale_files].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (pid, process) in s.processes() {
+///     for file in process.stale_files() {
+///         println!("[{}] {}: {:?}", pid, file.path.display(), file.reason);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaleFile {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Why the file is stale.
+    pub reason: StaleFileReason,
+}
+
+/// Resources used by a systemd unit and the limits set on it.
+///
+/// It is returned by [`SystemExt::systemd_units`][crate::SystemExt::systemd_units].
+///
+/// ```no_run
+/// use sysinfo::{Bytes, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for unit in s.systemd_units() {
+///     println!(
+///         "{}: {}% CPU, {} (limit: {:?})",
+///         unit.name,
+///         unit.usage.cpu_usage,
+///         Bytes(unit.usage.memory),
+///         unit.memory_limit.map(Bytes),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemdUnit {
+    /// Name of the unit (`nginx.service` for example).
+    pub name: String,
+    /// Control group of the unit.
+    pub cgroup: PathBuf,
+    /// Resources used by the processes of the unit.
+    pub usage: ProcessGroup,
+    /// Memory limit (in bytes) of the unit.
+    pub memory_limit: Option<u64>,
+    /// CPU limit of the unit (in %, `100.` being one CPU).
+    pub cpu_limit: Option<f32>,
+    /// Maximum number of tasks (processes and threads) of the unit.
+    pub tasks_limit: Option<u64>,
+}
+
+/// An amount of bytes, to convert or display memory values.
+///
+/// All the memory values returned by this crate are in bytes. Conversions use binary units
+/// (1 KiB being 1024 bytes).
+///
+/// ```
+/// use sysinfo::Bytes;
+///
+/// let size = Bytes(3 * 1024 * 1024 / 2);
+/// assert_eq!(size.as_kib(), 1536.);
+/// assert_eq!(size.as_mib(), 1.5);
+/// assert_eq!(size.to_string(), "1.50 MiB");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub u64);
+
+impl Bytes {
+    /// Returns the amount of bytes.
+    pub fn as_bytes(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the amount in KiB.
+    pub fn as_kib(self) -> f64 {
+        self.0 as f64 / 1024.
+    }
+
+    /// Returns the amount in MiB.
+    pub fn as_mib(self) -> f64 {
+        self.0 as f64 / (1024. * 1024.)
+    }
+
+    /// Returns the amount in GiB.
+    pub fn as_gib(self) -> f64 {
+        self.0 as f64 / (1024. * 1024. * 1024.)
+    }
+}
+
+impl From<u64> for Bytes {
+    fn from(bytes: u64) -> Bytes {
+        Bytes(bytes)
+    }
+}
+
+impl fmt::Display for Bytes {
+    /// Displays the amount with the biggest unit in which it is at least 1.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut value = self.as_kib();
+        let mut unit = 0;
+        while value >= 1024. && unit + 1 < UNITS.len() {
+            value /= 1024.;
+            unit += 1;
+        }
+        write!(f, "{:.2} {}", value, UNITS[unit])
+    }
+}
+
+/// Kernel version parsed from its release string, returned by
+/// [`SystemExt::parsed_kernel_version`][crate::SystemExt::parsed_kernel_version].
+///
+/// Versions are compared on their numbers only: a release candidate is lower than the release and
+/// the suffix is ignored (so `5.15.0-91-generic` is equal to `5.15.0-1-aws`). They can also be
+/// compared to strings, which are parsed the same way:
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(kernel) = s.parsed_kernel_version() {
+///     if kernel >= "5.3" {
+///         println!("pidfd_open is available on {}", kernel.machine);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct KernelVersion {
+    /// Major version.
+    pub major: u32,
+    /// Minor version, `0` if there is none.
+    pub minor: u32,
+    /// Patch version, `0` if there is none.
+    pub patch: u32,
+    /// Number of the release candidate (`6.8.0-rc3` for example).
+    pub rc: Option<u32>,
+    /// What follows the version numbers, usually added by the distribution (`91-generic` in
+    /// `5.15.0-91-generic`).
+    pub suffix: String,
+    /// Last part of the suffix if it isn't a number (`generic` in `5.15.0-91-generic`).
+    pub flavour: Option<String>,
+    /// Unparsed release string.
+    pub release: String,
+    /// Name of the kernel (`Linux` or `Darwin` for example). Empty on non-unix targets.
+    pub sysname: String,
+    /// Build information of the kernel (`#1 SMP PREEMPT_DYNAMIC ...` on linux). Empty on non-unix
+    /// targets.
+    pub version: String,
+    /// Hardware identifier (`x86_64` or `aarch64` for example). Empty on non-unix targets.
+    pub machine: String,
+}
+
+impl KernelVersion {
+    /// Parses a kernel release string. Only the `release`-derived fields are filled.
+    ///
+    /// ```
+    /// use sysinfo::KernelVersion;
+    ///
+    /// let kernel = KernelVersion::parse("6.8.0-rc3-amd64").expect("invalid version");
+    /// assert_eq!((kernel.major, kernel.minor, kernel.patch), (6, 8, 0));
+    /// assert_eq!(kernel.rc, Some(3));
+    /// assert_eq!(kernel.flavour.as_deref(), Some("amd64"));
+    /// ```
+    pub fn parse(release: &str) -> Option<KernelVersion> {
+        let release = release.trim();
+        let mut numbers = [0; 3];
+        let mut rest = release;
+
+        for (i, number) in numbers.iter_mut().enumerate() {
+            if i > 0 {
+                match rest.strip_prefix('.') {
+                    Some(r) if r.starts_with(|c: char| c.is_ascii_digit()) => rest = r,
+                    _ => break,
+                }
+            }
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            *number = rest[..end].parse().ok()?;
+            rest = &rest[end..];
+        }
+
+        let suffix = rest.trim_start_matches(|c| matches!(c, '-' | '.' | '+' | '_' | '~'));
+        let rc = suffix.strip_prefix("rc").and_then(|rc| {
+            let end = rc.find(|c: char| !c.is_ascii_digit()).unwrap_or(rc.len());
+            rc[..end].parse().ok()
+        });
+        let flavour = suffix
+            .rsplit('-')
+            .next()
+            .filter(|last| last.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter(|last| rc.is_none() || !last.starts_with("rc"))
+            .map(str::to_owned);
+
+        Some(KernelVersion {
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers[2],
+            rc,
+            suffix: suffix.to_owned(),
+            flavour,
+            release: release.to_owned(),
+            ..KernelVersion::default()
+        })
+    }
+
+    fn key(&self) -> (u32, u32, u32, u32) {
+        // A release candidate comes before the release.
+        (
+            self.major,
+            self.minor,
+            self.patch,
+            self.rc.unwrap_or(u32::MAX),
+        )
+    }
+}
+
+impl PartialEq for KernelVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for KernelVersion {}
+
+impl PartialOrd for KernelVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for KernelVersion {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialEq<&str> for KernelVersion {
+    fn eq(&self, other: &&str) -> bool {
+        KernelVersion::parse(other).map_or(false, |other| *self == other)
+    }
+}
+
+impl PartialOrd<&str> for KernelVersion {
+    /// Returns `None` if the string isn't a valid version.
+    fn partial_cmp(&self, other: &&str) -> Option<std::cmp::Ordering> {
+        KernelVersion::parse(other).map(|other| self.cmp(&other))
+    }
+}
+
+impl fmt::Display for KernelVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.release)
+    }
+}
+
+/// Detailed memory information, returned by
+/// [`SystemExt::memory_info`][crate::SystemExt::memory_info].
+///
+/// All the values are in bytes, except for the `huge_pages_*` counts. On linux, they come from
+/// `/proc/meminfo`. On other targets, only the `total`, `free`, `available`, `swap_total` and
+/// `swap_free` fields are filled.
+///
+/// ```no_run
+/// use sysinfo::{Bytes, System, SystemExt};
+///
+/// let s = System::new_all();
+/// let info = s.memory_info();
+/// if info.committed_as > info.commit_limit {
+///     println!("overcommitted by {}", Bytes(info.committed_as - info.commit_limit));
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryInfo {
+    /// Total usable memory.
+    pub total: u64,
+    /// Memory not used at all.
+    pub free: u64,
+    /// Estimation of the memory available to start new applications without swapping.
+    pub available: u64,
+    /// Memory used by the kernel for buffers.
+    pub buffers: u64,
+    /// Memory used by the page cache (`Cached`).
+    pub cached: u64,
+    /// Swapped out memory which is also in the page cache.
+    pub swap_cached: u64,
+    /// Memory used recently, not reclaimed unless needed.
+    pub active: u64,
+    /// Memory not used recently, which can be reclaimed.
+    pub inactive: u64,
+    /// Anonymous memory used recently.
+    pub active_anon: u64,
+    /// Anonymous memory not used recently.
+    pub inactive_anon: u64,
+    /// Page cache memory used recently.
+    pub active_file: u64,
+    /// Page cache memory not used recently.
+    pub inactive_file: u64,
+    /// Memory which cannot be reclaimed.
+    pub unevictable: u64,
+    /// Memory locked with `mlock`.
+    pub mlocked: u64,
+    /// Total swap space.
+    pub swap_total: u64,
+    /// Unused swap space.
+    pub swap_free: u64,
+    /// Memory waiting to be written back to the disk.
+    pub dirty: u64,
+    /// Memory being written back to the disk.
+    pub writeback: u64,
+    /// Anonymous memory mapped into userspace page tables.
+    pub anon_pages: u64,
+    /// Files mapped into memory (with `mmap`), such as libraries.
+    pub mapped: u64,
+    /// Shared memory (including `tmpfs`).
+    pub shmem: u64,
+    /// Kernel slab memory which might be reclaimed.
+    pub s_reclaimable: u64,
+    /// Kernel slab memory which cannot be reclaimed.
+    pub s_unreclaim: u64,
+    /// Memory used by the kernel stacks.
+    pub kernel_stack: u64,
+    /// Memory used by the page tables.
+    pub page_tables: u64,
+    /// Total memory which can be allocated before the overcommit limit is reached.
+    pub commit_limit: u64,
+    /// Total memory currently allocated by the processes, even if not used yet.
+    pub committed_as: u64,
+    /// Memory used by the vmalloc area.
+    pub vmalloc_used: u64,
+    /// Anonymous memory backed by transparent huge pages.
+    pub anon_huge_pages: u64,
+    /// Number of huge pages in the pool.
+    pub huge_pages_total: u64,
+    /// Number of huge pages not allocated.
+    pub huge_pages_free: u64,
+    /// Number of huge pages reserved but not allocated yet.
+    pub huge_pages_reserved: u64,
+    /// Number of huge pages allocated above the pool size.
+    pub huge_pages_surplus: u64,
+    /// Size of a huge page.
+    pub huge_page_size: u64,
+    /// The other keys and their values. The values which have a unit are in bytes.
+    pub others: HashMap<String, u64>,
+}
+
 /// Enum describing the different status of a process.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ProcessStatus {
@@ -606,7 +1600,7 @@ pub fn get_current_pid() -> Result<Pid, &'static str> {
 
 #[cfg(test)]
 mod tests {
-    use super::ProcessStatus;
+    use super::{Bytes, ChassisType, KernelVersion, ProcessStatus};
 
     // This test only exists to ensure that the `Display` trait is implemented on the
     // `ProcessStatus` enum on all targets.
@@ -614,4 +1608,60 @@ mod tests {
     fn check_display_impl_process_status() {
         println!("{} {:?}", ProcessStatus::Parked, ProcessStatus::Idle);
     }
+
+    #[test]
+    fn check_kernel_version() {
+        let parse = |release| KernelVersion::parse(release).expect("invalid version");
+
+        let kernel = parse("5.15.0-91-generic");
+        assert_eq!((kernel.major, kernel.minor, kernel.patch), (5, 15, 0));
+        assert_eq!(kernel.rc, None);
+        assert_eq!(kernel.suffix, "91-generic");
+        assert_eq!(kernel.flavour.as_deref(), Some("generic"));
+
+        let kernel = parse("6.8-rc3");
+        assert_eq!((kernel.major, kernel.minor, kernel.patch), (6, 8, 0));
+        assert_eq!(kernel.rc, Some(3));
+        assert_eq!(kernel.flavour, None);
+
+        let kernel = parse("5.14.0-362.8.1.el9_3.x86_64");
+        assert_eq!((kernel.major, kernel.minor, kernel.patch), (5, 14, 0));
+        assert_eq!(kernel.suffix, "362.8.1.el9_3.x86_64");
+        assert_eq!(kernel.flavour, None);
+
+        let kernel = parse("6.6.10-arch1-1");
+        assert_eq!(kernel.patch, 10);
+        assert_eq!(kernel.flavour, None);
+
+        assert_eq!(parse("21.6.0").to_string(), "21.6.0");
+        assert!(KernelVersion::parse("").is_none());
+        assert!(KernelVersion::parse("linux").is_none());
+
+        assert!(parse("6.8.0-rc3") < parse("6.8.0"));
+        assert!(parse("6.8.0") < parse("6.8.1-rc1"));
+        assert_eq!(parse("5.15.0-91-generic"), parse("5.15.0-1-aws"));
+        assert!(parse("5.10.0") > parse("5.9.20"));
+        assert!(parse("5.2.0") >= "5.2");
+        assert!(parse("4.19.0") < "4.20");
+        assert!(parse("5.2.0") == "5.2");
+        assert_eq!(parse("5.2.0").partial_cmp(&"invalid"), None);
+    }
+
+    #[test]
+    fn check_chassis_type() {
+        assert_eq!(ChassisType::from(3), ChassisType::Desktop);
+        assert_eq!(ChassisType::from(10), ChassisType::Laptop);
+        assert_eq!(ChassisType::from(23), ChassisType::Server);
+        assert_eq!(ChassisType::from(2), ChassisType::Other(2));
+    }
+
+    #[test]
+    fn check_display_bytes() {
+        assert_eq!(Bytes(0).to_string(), "0 B");
+        assert_eq!(Bytes(1_023).to_string(), "1023 B");
+        assert_eq!(Bytes(1_024).to_string(), "1.00 KiB");
+        assert_eq!(Bytes(10 * 1_024 * 1_024).to_string(), "10.00 MiB");
+        assert_eq!(Bytes(5 << 40).to_string(), "5.00 TiB");
+        assert_eq!(Bytes(u64::MAX).to_string(), "16.00 EiB");
+    }
 }
@@ -0,0 +1,134 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::{get_current_pid, Process};
+
+use std::time::Duration;
+
+/// Resources used by a process, as returned by `getrusage`.
+///
+/// All the values are `0` on non-unix targets.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResourceUsage {
+    /// Time spent in user mode.
+    pub user_time: Duration,
+    /// Time spent in kernel mode.
+    pub system_time: Duration,
+    /// Highest memory usage (in bytes). For terminated children, it is the peak of the biggest
+    /// one.
+    pub peak_memory: u64,
+    /// Number of page faults serviced without any I/O.
+    pub minor_page_faults: u64,
+    /// Number of page faults which required I/O.
+    pub major_page_faults: u64,
+    /// Number of times the process gave up the processor before the end of its time slice.
+    pub voluntary_context_switches: u64,
+    /// Number of times the process was preempted.
+    pub involuntary_context_switches: u64,
+}
+
+impl ResourceUsage {
+    /// Returns the sum of `user_time` and `system_time`.
+    pub fn cpu_time(&self) -> Duration {
+        self.user_time + self.system_time
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn from_rusage(rusage: &libc::rusage) -> ResourceUsage {
+        let timeval_to_duration = |t: libc::timeval| {
+            Duration::from_secs(t.tv_sec as u64) + Duration::from_micros(t.tv_usec as u64)
+        };
+
+        ResourceUsage {
+            user_time: timeval_to_duration(rusage.ru_utime),
+            system_time: timeval_to_duration(rusage.ru_stime),
+            // `ru_maxrss` is in bytes on apple targets and in KiB on the other ones.
+            peak_memory: if cfg!(any(target_os = "macos", target_os = "ios")) {
+                rusage.ru_maxrss as u64
+            } else {
+                rusage.ru_maxrss as u64 * 1_024
+            },
+            minor_page_faults: rusage.ru_minflt as u64,
+            major_page_faults: rusage.ru_majflt as u64,
+            voluntary_context_switches: rusage.ru_nvcsw as u64,
+            involuntary_context_switches: rusage.ru_nivcsw as u64,
+        }
+    }
+
+    #[cfg(unix)]
+    fn get(who: std::os::raw::c_int) -> ResourceUsage {
+        let mut rusage = unsafe { std::mem::zeroed::<libc::rusage>() };
+        if unsafe { libc::getrusage(who, &mut rusage) } != 0 {
+            return ResourceUsage::default();
+        }
+        ResourceUsage::from_rusage(&rusage)
+    }
+}
+
+/// Information about the current process, returned by [`current_process`].
+#[derive(Debug)]
+pub struct CurrentProcess {
+    /// The current process. Its CPU usage is `0.` since it needs two refreshes to be computed.
+    pub process: Process,
+    /// Number of file descriptors (or handles) opened by the process. It is `0` on targets other
+    /// than linux.
+    pub fd_count: usize,
+    /// Resources used by the process itself.
+    pub self_usage: ResourceUsage,
+    /// Resources used by the terminated and waited-for children of the process.
+    pub children_usage: ResourceUsage,
+}
+
+/// Returns information about the current process.
+///
+/// It is much cheaper than creating a [`System`][crate::System] and refreshing the process: on
+/// linux, only the files in `/proc/self` are read and nothing is retrieved about the processors or
+/// the other processes.
+///
+/// ```no_run
+/// let current = sysinfo::current_process().expect("failed to get the current process");
+/// println!(
+///     "{}, {:?} CPU time, {} opened files",
+///     sysinfo::Bytes(sysinfo::ProcessExt::memory(&current.process)),
+///     current.self_usage.cpu_time(),
+///     current.fd_count,
+/// );
+/// ```
+pub fn current_process() -> Option<CurrentProcess> {
+    let pid = get_current_pid().ok()?;
+
+    cfg_if::cfg_if! {
+        if #[cfg(all(any(target_os = "linux", target_os = "android"), not(feature = "unknown-ci")))] {
+            let process = crate::sys::process::current_process(pid)?;
+            // The directory we're reading is an opened file descriptor too.
+            let fd_count = std::fs::read_dir("/proc/self/fd")
+                .map(|dir| dir.count().saturating_sub(1))
+                .unwrap_or(0);
+        } else {
+            use crate::{ProcessRefreshKind, System, SystemExt};
+
+            let mut system = System::new();
+            if !system.refresh_process_specifics(pid, ProcessRefreshKind::everything()) {
+                return None;
+            }
+            let process = system.take_process(pid)?;
+            let fd_count = 0;
+        }
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(unix)] {
+            let self_usage = ResourceUsage::get(libc::RUSAGE_SELF);
+            let children_usage = ResourceUsage::get(libc::RUSAGE_CHILDREN);
+        } else {
+            let self_usage = ResourceUsage::default();
+            let children_usage = ResourceUsage::default();
+        }
+    }
+
+    Some(CurrentProcess {
+        process,
+        fd_count,
+        self_usage,
+        children_usage,
+    })
+}
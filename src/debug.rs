@@ -72,7 +72,11 @@ impl fmt::Debug for Process {
             .field("memory usage", &self.memory())
             .field("virtual memory usage", &self.virtual_memory())
             .field("CPU usage", &self.cpu_usage())
+            .field("user CPU usage", &self.user_cpu_usage())
+            .field("system CPU usage", &self.system_cpu_usage())
+            .field("total CPU time", &self.total_cpu_time())
             .field("status", &self.status())
+            .field("kernel thread", &self.is_kernel_thread())
             .field("root", &self.root())
             .field("disk_usage", &self.disk_usage())
             .finish()
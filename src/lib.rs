@@ -62,10 +62,21 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(feature = "regex")]
+extern crate regex_crate as regex;
+
+pub use cgroups::{Cgroup, CgroupIo, Cgroups, CgroupsIter};
 pub use common::{
-    get_current_pid, AsU32, DiskType, DiskUsage, Gid, LoadAvg, NetworksIter, Pid, ProcessStatus,
-    RefreshKind, Signal, Uid, User,
+    get_current_pid, AsU32, Bytes, ChassisType, DiskType, DiskUsage, Gid, HardwareInfo,
+    KernelVersion, LoadAvg, LoginRecord, MemoryInfo, NetworksIter, NumaNode, OsRelease, Pid,
+    Pressure, PressureStats, ProcessRefreshKind, ProcessStatus, RefreshKind, ResourcePressure,
+    Session, Signal, StaleFile, StaleFileReason, Swap, SwapType, SystemdUnit, Uid, User,
+    Virtualization, VmCounter, VmStats, ZramStats,
 };
+pub use current::{current_process, CurrentProcess, ResourceUsage};
+pub use measure::{measure, measure_pid, Measurement};
+pub use monitor::{ProcessMonitor, ProcessSample};
+pub use query::{ProcessGroup, ProcessQuery, ProcessSortKey};
 pub use sys::{Component, Disk, NetworkData, Networks, Process, Processor, System};
 pub use traits::{
     ComponentExt, DiskExt, NetworkExt, NetworksExt, ProcessExt, ProcessorExt, SystemExt, UserExt,
@@ -76,8 +87,13 @@ pub use c_interface::*;
 
 #[cfg(feature = "c-interface")]
 mod c_interface;
+mod cgroups;
 mod common;
+mod current;
 mod debug;
+mod measure;
+mod monitor;
+mod query;
 mod system;
 mod traits;
 mod utils;
@@ -173,6 +189,17 @@ mod test {
         if System::IS_SUPPORTED {
             assert!(s.total_memory() > 0);
             assert!(s.used_memory() > 0);
+            assert_eq!(s.memory_info().total, s.total_memory());
+            assert_eq!(s.memory_info().swap_total, s.total_swap());
+            if cfg!(target_os = "linux") {
+                // `/proc/meminfo` values are in KiB.
+                assert_eq!(s.total_memory() % 1_024, 0);
+                assert!(s.vm_stats().pgfault.total > 0);
+                assert_eq!(
+                    s.swaps().iter().map(|s| s.size).sum::<u64>(),
+                    s.total_swap()
+                );
+            }
             if s.total_swap() > 0 {
                 // I think it's pretty safe to assume that there is still some swap left...
                 assert!(s.free_swap() > 0);
@@ -230,6 +257,22 @@ mod test {
         assert!(s.users().len() >= MIN_USERS);
     }
 
+    #[test]
+    fn check_sessions() {
+        let s = System::new();
+        let sessions = s.sessions();
+        let history = s.login_history();
+
+        if !System::IS_SUPPORTED || !cfg!(target_os = "linux") {
+            assert!(sessions.is_empty());
+            assert!(history.is_empty());
+        }
+        assert!(sessions.iter().all(|session| !session.user.is_empty()));
+        assert!(history
+            .iter()
+            .all(|r| r.logout_time.map_or(true, |t| t >= r.session.login_time)));
+    }
+
     #[test]
     fn check_uid_gid() {
         let mut s = System::new();
@@ -259,6 +302,58 @@ mod test {
         }
     }
 
+    #[test]
+    fn check_effective_limits() {
+        let mut s = System::new();
+        s.refresh_memory();
+
+        assert!(s.effective_memory_limit() <= s.total_memory());
+        if System::IS_SUPPORTED {
+            assert!(s.effective_memory_limit() > 0);
+            assert!(s.effective_memory_usage() > 0);
+            assert!(s.effective_cpu_count() >= 1);
+            assert!(s.effective_cpu_count() <= s.processors().len());
+        }
+    }
+
+    #[test]
+    fn check_pressure() {
+        let s = System::new();
+        let pressure = s.pressure();
+
+        if cfg!(all(target_os = "linux", not(feature = "unknown-ci")))
+            && std::path::Path::new("/proc/pressure/cpu").exists()
+        {
+            assert!(pressure.cpu.and_then(|cpu| cpu.some).is_some());
+            assert!(pressure.memory.and_then(|memory| memory.full).is_some());
+        } else {
+            assert_eq!(pressure, Pressure::default());
+        }
+    }
+
+    #[test]
+    fn check_virtualization() {
+        let virtualization = System::new().virtualization();
+
+        assert!(!(virtualization.is_vm() && virtualization.is_container()));
+        if !System::IS_SUPPORTED || !cfg!(target_os = "linux") {
+            assert_eq!(virtualization, Virtualization::None);
+        } else if std::path::Path::new("/.dockerenv").exists() {
+            assert!(virtualization.is_container());
+        }
+    }
+
+    #[test]
+    fn check_hardware_info() {
+        let hardware = System::new().hardware_info();
+
+        if !System::IS_SUPPORTED || !cfg!(target_os = "linux") {
+            assert_eq!(hardware, HardwareInfo::default());
+        } else if std::path::Path::new("/sys/class/dmi/id/bios_date").exists() {
+            assert!(!hardware.bios_date.is_empty());
+        }
+    }
+
     #[test]
     fn check_system_info() {
         // We don't want to test on unsupported systems.
@@ -277,6 +372,22 @@ mod test {
                 .long_os_version()
                 .expect("Failed to get long OS version")
                 .is_empty());
+
+            let kernel = s
+                .parsed_kernel_version()
+                .expect("Failed to parse kernel version");
+            assert_eq!(Some(kernel.to_string()), s.kernel_version());
+            if cfg!(target_os = "linux") {
+                assert_eq!(kernel.sysname, "Linux");
+                assert!(!kernel.machine.is_empty());
+                assert!(kernel >= "2.6");
+            }
+
+            // `/usr/lib/os-release` is used if `/etc/os-release` doesn't exist.
+            if let Some(os) = s.os_release() {
+                assert_eq!(Some(os.name), s.name());
+                assert!(!os.id.is_empty());
+            }
         }
     }
 
@@ -0,0 +1,630 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::cgroups::{Cgroup, Counter};
+use crate::sys::numa::parse_cpu_list;
+use crate::sys::process::parse_cgroup_file;
+use crate::sys::utils::get_all_data;
+use crate::Pid;
+
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+/// Kinds of units which can own processes (slices are handled separately).
+const UNIT_SUFFIXES: &[&str] = &[
+    ".service",
+    ".scope",
+    ".socket",
+    ".mount",
+    ".swap",
+    ".device",
+    ".target",
+    ".timer",
+    ".path",
+    ".automount",
+];
+
+/// Mount points of the cgroup hierarchies we're interested into.
+#[derive(Default)]
+struct Hierarchies {
+    unified: Option<PathBuf>,
+    memory: Option<PathBuf>,
+    cpu: Option<PathBuf>,
+    cpuacct: Option<PathBuf>,
+    cpuset: Option<PathBuf>,
+    blkio: Option<PathBuf>,
+    pids: Option<PathBuf>,
+}
+
+static HIERARCHIES: Lazy<Hierarchies> = Lazy::new(|| {
+    let mut h = Hierarchies::default();
+    let data = match get_all_data("/proc/mounts", 16_385) {
+        Ok(data) => data,
+        Err(_) => return h,
+    };
+
+    for line in data.lines() {
+        let mut fields = line.split_whitespace().skip(1);
+        let (mount_point, fs_type, options) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(m), Some(f), Some(o)) => (PathBuf::from(m), f, o),
+            _ => continue,
+        };
+        match fs_type {
+            "cgroup2" => h.unified = Some(mount_point),
+            "cgroup" => {
+                for option in options.split(',') {
+                    let controller = match option {
+                        "memory" => &mut h.memory,
+                        "cpu" => &mut h.cpu,
+                        "cpuacct" => &mut h.cpuacct,
+                        "cpuset" => &mut h.cpuset,
+                        "blkio" => &mut h.blkio,
+                        "pids" => &mut h.pids,
+                        _ => continue,
+                    };
+                    *controller = Some(mount_point.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    h
+});
+
+/// Limits set on a control group.
+#[derive(Default)]
+pub(crate) struct CgroupLimits {
+    /// In bytes.
+    pub(crate) memory: Option<u64>,
+    /// In percent, 100% being one CPU.
+    pub(crate) cpu: Option<f32>,
+    pub(crate) tasks: Option<u64>,
+}
+
+/// Returns the path of `file` for the `cgroup` control group. Controllers mounted as a cgroup v1
+/// hierarchy aren't available in the unified hierarchy.
+fn controller_file(v1_root: &Option<PathBuf>, cgroup: &Path, file: &str) -> Option<PathBuf> {
+    let root = v1_root.as_ref().or_else(|| HIERARCHIES.unified.as_ref())?;
+    let mut path = root.join(cgroup.strip_prefix("/").unwrap_or(cgroup));
+    path.push(file);
+    Some(path)
+}
+
+fn read_value(path: Option<PathBuf>) -> Option<String> {
+    get_all_data(path?, 128)
+        .ok()
+        .map(|data| data.trim().to_owned())
+}
+
+/// Parses a value which can be "max" (in which case there is no limit). Cgroup v1 doesn't use
+/// "max" but a huge value for the memory.
+fn parse_limit(value: &str) -> Option<u64> {
+    match u64::from_str(value) {
+        Ok(v) if v < i64::MAX as u64 & !0xFFF => Some(v),
+        _ => None,
+    }
+}
+
+pub(crate) fn read_limits(cgroup: &Path) -> CgroupLimits {
+    let h = &*HIERARCHIES;
+    let memory = if h.memory.is_some() {
+        read_value(controller_file(&h.memory, cgroup, "memory.limit_in_bytes"))
+    } else {
+        read_value(controller_file(&None, cgroup, "memory.max"))
+    };
+    let cpu = read_cpu_limit(cgroup);
+    let tasks = read_value(controller_file(&h.pids, cgroup, "pids.max"));
+
+    CgroupLimits {
+        memory: memory.as_deref().and_then(parse_limit),
+        cpu,
+        tasks: tasks.as_deref().and_then(parse_limit),
+    }
+}
+
+fn read_cpu_limit(cgroup: &Path) -> Option<f32> {
+    let h = &*HIERARCHIES;
+
+    if h.cpu.is_some() {
+        read_value(controller_file(&h.cpu, cgroup, "cpu.cfs_quota_us")).and_then(|quota| {
+            let period = read_value(controller_file(&h.cpu, cgroup, "cpu.cfs_period_us"))?;
+            parse_cpu_limit(&quota, &period)
+        })
+    } else {
+        read_value(controller_file(&None, cgroup, "cpu.max")).and_then(|data| {
+            let mut parts = data.split_whitespace();
+            parse_cpu_limit(parts.next()?, parts.next()?)
+        })
+    }
+}
+
+/// `quota` is "max" (cgroup v2) or "-1" (cgroup v1) if there is no limit.
+fn parse_cpu_limit(quota: &str, period: &str) -> Option<f32> {
+    match (u64::from_str(quota), u64::from_str(period)) {
+        (Ok(quota), Ok(period)) if period != 0 => Some(quota as f32 / period as f32 * 100.),
+        _ => None,
+    }
+}
+
+/// Returns the control group of the current process for `controller`.
+pub(crate) fn current_cgroup(controller: &str) -> Option<PathBuf> {
+    let data = get_all_data("/proc/self/cgroup", 1_024).ok()?;
+    let h = &*HIERARCHIES;
+    let v1_root = match controller {
+        "memory" => &h.memory,
+        "cpu" => &h.cpu,
+        "cpuset" => &h.cpuset,
+        "pids" => &h.pids,
+        _ => &None,
+    };
+
+    if v1_root.is_some() {
+        v1_cgroup(&data, controller)
+    } else {
+        Some(parse_cgroup_file(&data))
+    }
+}
+
+/// With cgroup v1, each hierarchy has its own path in the `cgroup` file.
+fn v1_cgroup(data: &str, controller: &str) -> Option<PathBuf> {
+    data.lines().find_map(|line| {
+        let mut parts = line.splitn(3, ':').skip(1);
+        match (parts.next(), parts.next()) {
+            (Some(controllers), Some(path)) if controllers.split(',').any(|c| c == controller) => {
+                Some(PathBuf::from(path))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Returns the lowest memory limit (in bytes) of `cgroup` and its ancestors, along with the
+/// memory usage (in bytes) of the control group which has this limit.
+///
+/// Inside a container, the control group of the current process might not exist in the mounted
+/// hierarchy (which starts at the control group of the container), in which case the limits of
+/// the root of the hierarchy are used.
+pub(crate) fn memory_limit_and_usage(cgroup: &Path) -> Option<(u64, Option<u64>)> {
+    let h = &*HIERARCHIES;
+    let (limit_file, usage_file) = if h.memory.is_some() {
+        ("memory.limit_in_bytes", "memory.usage_in_bytes")
+    } else {
+        ("memory.max", "memory.current")
+    };
+    let mut lowest: Option<(u64, &Path)> = None;
+
+    for ancestor in cgroup.ancestors() {
+        let limit = read_value(controller_file(&h.memory, ancestor, limit_file))
+            .as_deref()
+            .and_then(parse_limit);
+        match (limit, lowest) {
+            (Some(limit), Some((lowest_limit, _))) if limit >= lowest_limit => {}
+            (Some(limit), _) => lowest = Some((limit, ancestor)),
+            _ => {}
+        }
+    }
+    lowest.map(|(limit, ancestor)| {
+        let usage = read_value(controller_file(&h.memory, ancestor, usage_file))
+            .and_then(|usage| u64::from_str(&usage).ok());
+        (limit, usage)
+    })
+}
+
+/// Returns the lowest CPU limit (in %) of `cgroup` and its ancestors.
+pub(crate) fn cpu_limit(cgroup: &Path) -> Option<f32> {
+    cgroup
+        .ancestors()
+        .filter_map(read_cpu_limit)
+        .fold(None, |lowest: Option<f32>, limit| {
+            Some(lowest.map_or(limit, |lowest| lowest.min(limit)))
+        })
+}
+
+/// Returns the CPUs the `cgroup` control group can use.
+pub(crate) fn cpuset(cgroup: &Path) -> Option<Vec<usize>> {
+    let h = &*HIERARCHIES;
+    let file = if h.cpuset.is_some() {
+        "cpuset.effective_cpus"
+    } else {
+        "cpuset.cpus.effective"
+    };
+
+    cgroup
+        .ancestors()
+        .filter_map(|ancestor| read_value(controller_file(&h.cpuset, ancestor, file)))
+        .map(|cpus| parse_cpu_list(&cpus))
+        .find(|cpus| !cpus.is_empty())
+}
+
+/// Returns the path of the Pressure Stall Information file of `resource` for the `cgroup` control
+/// group. It is only available in the unified hierarchy.
+pub(crate) fn pressure_file(cgroup: &Path, resource: &str) -> Option<PathBuf> {
+    controller_file(&None, cgroup, &format!("{}.pressure", resource))
+}
+
+impl Counter {
+    /// The first update sets both values so the difference starts at `0`.
+    pub(crate) fn update(&mut self, value: u64) {
+        self.old = self.new.unwrap_or(value);
+        self.new = Some(value);
+    }
+}
+
+/// Walks the cgroup hierarchies to add the new control groups and remove the ones which don't
+/// exist anymore, then refreshes them.
+pub(crate) fn refresh_cgroups_list(cgroups: &mut HashMap<PathBuf, Cgroup>) {
+    let paths = list_cgroups();
+    cgroups.retain(|path, _| paths.contains(path));
+    for path in paths {
+        cgroups.entry(path).or_default();
+    }
+    refresh_cgroups(cgroups);
+}
+
+pub(crate) fn refresh_cgroups(cgroups: &mut HashMap<PathBuf, Cgroup>) {
+    for (path, cgroup) in cgroups.iter_mut() {
+        refresh_cgroup(path, cgroup);
+    }
+}
+
+/// Returns the paths of all the control groups of the mounted hierarchies. With cgroup v1, a path
+/// is returned if it exists in at least one of the hierarchies.
+fn list_cgroups() -> HashSet<PathBuf> {
+    let h = &*HIERARCHIES;
+    let mut cgroups = HashSet::new();
+
+    for root in [&h.unified, &h.cpu, &h.cpuacct, &h.memory, &h.blkio, &h.pids]
+        .iter()
+        .filter_map(|root| root.as_ref())
+    {
+        walk_cgroups(root, root, &mut cgroups);
+    }
+    cgroups
+}
+
+fn walk_cgroups(root: &Path, dir: &Path, cgroups: &mut HashSet<PathBuf>) {
+    if let Ok(relative) = dir.strip_prefix(root) {
+        cgroups.insert(Path::new("/").join(relative));
+    }
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            walk_cgroups(root, &entry.path(), cgroups);
+        }
+    }
+}
+
+fn read_file(path: Option<PathBuf>) -> Option<String> {
+    get_all_data(path?, 4_096).ok()
+}
+
+fn read_u64(path: Option<PathBuf>) -> u64 {
+    read_value(path)
+        .and_then(|value| u64::from_str(&value).ok())
+        .unwrap_or(0)
+}
+
+/// Returns the value of `key` in a flat keyed file (`cpu.stat` or `memory.events` for example).
+fn keyed_value(data: &str, key: &str) -> Option<u64> {
+    data.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some(k), Some(value)) if k == key => u64::from_str(value).ok(),
+            _ => None,
+        }
+    })
+}
+
+/// Refreshes the statistics of the `path` control group.
+fn refresh_cgroup(path: &Path, cgroup: &mut Cgroup) {
+    let h = &*HIERARCHIES;
+
+    // With cgroup v1, a control group might only exist in some of the hierarchies.
+    cgroup.pids = [&None, &h.pids, &h.cpu, &h.memory]
+        .iter()
+        .find_map(|root| read_file(controller_file(root, path, "cgroup.procs")))
+        .map(|data| parse_pids(&data))
+        .unwrap_or_default();
+
+    let cpu_stat = read_file(controller_file(&h.cpu, path, "cpu.stat")).unwrap_or_default();
+    let throttled_time = if h.cpu.is_some() {
+        cgroup
+            .cpu_usage
+            .update(read_u64(controller_file(&h.cpuacct, path, "cpuacct.usage")) / 1_000);
+        keyed_value(&cpu_stat, "throttled_time").unwrap_or(0) / 1_000
+    } else {
+        cgroup
+            .cpu_usage
+            .update(keyed_value(&cpu_stat, "usage_usec").unwrap_or(0));
+        keyed_value(&cpu_stat, "throttled_usec").unwrap_or(0)
+    };
+    cgroup.throttled_time.update(throttled_time);
+    cgroup
+        .periods
+        .update(keyed_value(&cpu_stat, "nr_periods").unwrap_or(0));
+    cgroup
+        .throttled_periods
+        .update(keyed_value(&cpu_stat, "nr_throttled").unwrap_or(0));
+
+    if h.memory.is_some() {
+        cgroup.memory = read_u64(controller_file(&h.memory, path, "memory.usage_in_bytes"));
+        cgroup.memory_limit = read_value(controller_file(&h.memory, path, "memory.limit_in_bytes"))
+            .as_deref()
+            .and_then(parse_limit);
+        let oom_control =
+            read_file(controller_file(&h.memory, path, "memory.oom_control")).unwrap_or_default();
+        cgroup
+            .oom_kills
+            .update(keyed_value(&oom_control, "oom_kill").unwrap_or(0));
+    } else {
+        cgroup.memory = read_u64(controller_file(&None, path, "memory.current"));
+        cgroup.memory_limit = read_value(controller_file(&None, path, "memory.max"))
+            .as_deref()
+            .and_then(parse_limit);
+        let events = read_file(controller_file(&None, path, "memory.events")).unwrap_or_default();
+        cgroup.ooms.update(keyed_value(&events, "oom").unwrap_or(0));
+        cgroup
+            .oom_kills
+            .update(keyed_value(&events, "oom_kill").unwrap_or(0));
+    }
+
+    let io = if h.blkio.is_some() {
+        let bytes = read_file(controller_file(
+            &h.blkio,
+            path,
+            "blkio.throttle.io_service_bytes",
+        ));
+        let operations = read_file(controller_file(
+            &h.blkio,
+            path,
+            "blkio.throttle.io_serviced",
+        ));
+        parse_blkio(
+            bytes.as_deref().unwrap_or(""),
+            operations.as_deref().unwrap_or(""),
+        )
+    } else {
+        parse_io_stat(&read_file(controller_file(&None, path, "io.stat")).unwrap_or_default())
+    };
+    cgroup.io.retain(|device, _| io.contains_key(device));
+    for (device, [read_bytes, written_bytes, read_operations, write_operations]) in io {
+        let stats = cgroup.io.entry(device).or_default();
+        stats.read_bytes.update(read_bytes);
+        stats.written_bytes.update(written_bytes);
+        stats.read_operations.update(read_operations);
+        stats.write_operations.update(write_operations);
+    }
+
+    cgroup.tasks = read_u64(controller_file(&h.pids, path, "pids.current"));
+    cgroup.tasks_limit = read_value(controller_file(&h.pids, path, "pids.max"))
+        .as_deref()
+        .and_then(parse_limit);
+}
+
+fn parse_pids(data: &str) -> Vec<Pid> {
+    data.lines()
+        .filter_map(|line| Pid::from_str(line.trim()).ok())
+        .collect()
+}
+
+/// Parses the cgroup v2 `io.stat` file. The values are the read bytes, written bytes, read
+/// operations and write operations.
+fn parse_io_stat(data: &str) -> HashMap<String, [u64; 4]> {
+    let mut io = HashMap::new();
+
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let device = match parts.next() {
+            Some(device) => device,
+            None => continue,
+        };
+        let mut values = [0; 4];
+        for part in parts {
+            let mut kv = part.splitn(2, '=');
+            let index = match kv.next() {
+                Some("rbytes") => 0,
+                Some("wbytes") => 1,
+                Some("rios") => 2,
+                Some("wios") => 3,
+                _ => continue,
+            };
+            values[index] = kv.next().and_then(|v| u64::from_str(v).ok()).unwrap_or(0);
+        }
+        io.insert(device.to_owned(), values);
+    }
+    io
+}
+
+/// Parses the cgroup v1 `blkio.throttle.io_service_bytes` and `blkio.throttle.io_serviced` files,
+/// returning the same values as [`parse_io_stat`].
+fn parse_blkio(bytes: &str, operations: &str) -> HashMap<String, [u64; 4]> {
+    let mut io: HashMap<String, [u64; 4]> = HashMap::new();
+
+    for (data, offset) in [(bytes, 0), (operations, 2)].iter() {
+        for line in data.lines() {
+            let mut parts = line.split_whitespace();
+            let (device, op, value) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(device), Some(op), Some(value)) => (device, op, value),
+                // The last line is the total of all devices.
+                _ => continue,
+            };
+            let index = match op {
+                "Read" => *offset,
+                "Write" => offset + 1,
+                _ => continue,
+            };
+            io.entry(device.to_owned()).or_default()[index] = u64::from_str(value).unwrap_or(0);
+        }
+    }
+    io
+}
+
+/// Returns `true` if the system has been booted with systemd.
+pub(crate) fn is_systemd_booted() -> bool {
+    Path::new("/run/systemd/system").is_dir()
+}
+
+/// Returns the name of the unit owning the `cgroup` control group, along with the control group of
+/// this unit.
+///
+/// Like systemd, the owner is the first unit which isn't a slice in the path. If the path is only
+/// made of slices, the last one is the owner.
+pub(crate) fn systemd_unit(cgroup: &Path) -> Option<(String, PathBuf)> {
+    let mut unit_cgroup = PathBuf::from("/");
+    let mut slice = None;
+
+    for component in cgroup.components() {
+        let name = match component {
+            Component::RootDir => continue,
+            Component::Normal(name) => name.to_str()?,
+            _ => return None,
+        };
+        unit_cgroup.push(name);
+        if name.ends_with(".slice") {
+            slice = Some((name.to_owned(), unit_cgroup.clone()));
+        } else if UNIT_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)) {
+            return Some((name.to_owned(), unit_cgroup));
+        } else {
+            break;
+        }
+    }
+    match slice {
+        Some(slice) => Some(slice),
+        None if cgroup == Path::new("/") => Some(("-.slice".to_owned(), PathBuf::from("/"))),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        keyed_value, parse_blkio, parse_cpu_limit, parse_io_stat, parse_limit, systemd_unit,
+        v1_cgroup, Counter,
+    };
+    use std::path::{Path, PathBuf};
+
+    fn unit(path: &str) -> Option<(String, PathBuf)> {
+        systemd_unit(Path::new(path))
+    }
+
+    #[test]
+    fn test_systemd_unit() {
+        assert_eq!(
+            unit("/system.slice/nginx.service"),
+            Some((
+                "nginx.service".to_owned(),
+                "/system.slice/nginx.service".into()
+            )),
+        );
+        assert_eq!(
+            unit("/user.slice/user-1000.slice/user@1000.service/app.slice/foo.service"),
+            Some((
+                "user@1000.service".to_owned(),
+                "/user.slice/user-1000.slice/user@1000.service".into()
+            )),
+        );
+        assert_eq!(
+            unit("/user.slice/user-1000.slice/session-2.scope"),
+            Some((
+                "session-2.scope".to_owned(),
+                "/user.slice/user-1000.slice/session-2.scope".into()
+            )),
+        );
+        assert_eq!(
+            unit("/system.slice/docker-1234.scope/sub"),
+            Some((
+                "docker-1234.scope".to_owned(),
+                "/system.slice/docker-1234.scope".into()
+            )),
+        );
+        assert_eq!(
+            unit("/machine.slice"),
+            Some(("machine.slice".to_owned(), "/machine.slice".into())),
+        );
+        assert_eq!(unit("/"), Some(("-.slice".to_owned(), "/".into())));
+        assert_eq!(
+            unit("/init.scope"),
+            Some(("init.scope".to_owned(), "/init.scope".into()))
+        );
+        assert_eq!(unit("/docker/1234"), None);
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        assert_eq!(parse_limit("max"), None);
+        assert_eq!(parse_limit("9223372036854771712"), None);
+        assert_eq!(parse_limit("1073741824"), Some(1_073_741_824));
+        assert_eq!(parse_cpu_limit("max", "100000"), None);
+        assert_eq!(parse_cpu_limit("-1", "100000"), None);
+        assert_eq!(parse_cpu_limit("150000", "100000"), Some(150.));
+    }
+
+    #[test]
+    fn test_v1_cgroup() {
+        let data = "9:name=systemd:/
+4:memory:/docker/1234
+2:cpu,cpuacct:/docker/5678
+0::/
+";
+        assert_eq!(v1_cgroup(data, "memory"), Some("/docker/1234".into()));
+        assert_eq!(v1_cgroup(data, "cpu"), Some("/docker/5678".into()));
+        assert_eq!(v1_cgroup(data, "cpuset"), None);
+    }
+
+    #[test]
+    fn test_parse_cgroup_stats() {
+        let cpu_stat = "usage_usec 1234
+user_usec 1000
+system_usec 234
+nr_periods 10
+nr_throttled 3
+throttled_usec 500
+";
+        assert_eq!(keyed_value(cpu_stat, "usage_usec"), Some(1_234));
+        assert_eq!(keyed_value(cpu_stat, "nr_throttled"), Some(3));
+        assert_eq!(keyed_value(cpu_stat, "throttled_time"), None);
+
+        let io = parse_io_stat(
+            "8:0 rbytes=4096 wbytes=8192 rios=1 wios=2 dbytes=0 dios=0
+253:0 rbytes=12 wbytes=0 rios=3 wios=0 dbytes=0 dios=0
+",
+        );
+        assert_eq!(io.len(), 2);
+        assert_eq!(io["8:0"], [4_096, 8_192, 1, 2]);
+        assert_eq!(io["253:0"], [12, 0, 3, 0]);
+
+        let io = parse_blkio(
+            "8:0 Read 4096
+8:0 Write 8192
+8:0 Sync 0
+8:0 Async 12288
+8:0 Total 12288
+Total 12288
+",
+            "8:0 Read 1
+8:0 Write 2
+8:0 Total 3
+Total 3
+",
+        );
+        assert_eq!(io.len(), 1);
+        assert_eq!(io["8:0"], [4_096, 8_192, 1, 2]);
+    }
+
+    #[test]
+    fn test_counter_update() {
+        let mut counter = Counter::default();
+        // The first update sets both values.
+        counter.update(10);
+        assert_eq!((counter.old, counter.new), (10, Some(10)));
+        counter.update(25);
+        assert_eq!((counter.old, counter.new), (10, Some(25)));
+        counter.update(5);
+        assert_eq!((counter.old, counter.new), (25, Some(5)));
+    }
+}
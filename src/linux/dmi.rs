@@ -0,0 +1,69 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::sys::utils::get_all_data;
+use crate::{ChassisType, HardwareInfo};
+
+use std::path::Path;
+use std::str::FromStr;
+
+/// Values which vendors leave in the DMI tables instead of the actual information.
+const PLACEHOLDERS: &[&str] = &[
+    "To be filled by O.E.M.",
+    "To Be Filled By O.E.M.",
+    "Default string",
+    "Not Specified",
+    "Not Applicable",
+    "System Product Name",
+    "System manufacturer",
+    "System Version",
+    "None",
+    "N/A",
+];
+
+/// Reads the `name` file of `/sys/class/dmi/id`. Placeholder values are considered as missing.
+pub(crate) fn read(name: &str) -> Option<String> {
+    let value = get_all_data(Path::new("/sys/class/dmi/id").join(name), 128).ok()?;
+    clean(&value)
+}
+
+fn clean(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || PLACEHOLDERS.contains(&value) {
+        None
+    } else {
+        Some(value.to_owned())
+    }
+}
+
+pub(crate) fn get_hardware_info() -> HardwareInfo {
+    let or_empty = |name| read(name).unwrap_or_default();
+
+    HardwareInfo {
+        system_vendor: or_empty("sys_vendor"),
+        product_name: or_empty("product_name"),
+        product_version: or_empty("product_version"),
+        product_serial: read("product_serial"),
+        product_uuid: read("product_uuid"),
+        board_vendor: or_empty("board_vendor"),
+        board_name: or_empty("board_name"),
+        bios_vendor: or_empty("bios_vendor"),
+        bios_version: or_empty("bios_version"),
+        bios_date: or_empty("bios_date"),
+        chassis_type: read("chassis_type")
+            .and_then(|code| u8::from_str(&code).ok())
+            .map(ChassisType::from),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::clean;
+
+    #[test]
+    fn test_clean() {
+        assert_eq!(clean("LENOVO\n"), Some("LENOVO".to_owned()));
+        assert_eq!(clean("To be filled by O.E.M.\n"), None);
+        assert_eq!(clean("Default string"), None);
+        assert_eq!(clean("\n"), None);
+    }
+}
@@ -1,13 +1,22 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+pub(crate) mod cgroup;
 pub mod component;
 pub mod disk;
+pub(crate) mod dmi;
+pub(crate) mod monitor;
 pub mod network;
+pub(crate) mod numa;
+#[cfg(not(target_os = "android"))]
+pub(crate) mod os_release;
 pub mod process;
 pub mod processor;
+pub(crate) mod swap;
 pub mod system;
 pub mod users;
 pub(crate) mod utils;
+pub(crate) mod utmp;
+pub(crate) mod virtualization;
 
 pub use self::component::Component;
 pub use self::disk::Disk;
@@ -0,0 +1,161 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::sys::process::ticks_to_duration;
+use crate::{Pid, ProcessSample, ProcessStatus};
+
+use libc::{sysconf, _SC_PAGESIZE};
+use std::fs::File;
+use std::os::unix::fs::FileExt;
+use std::str::{self, FromStr};
+use std::time::Instant;
+
+/// Monitors a single process to sample its resources usage at a high frequency.
+///
+/// The `stat`, `statm`, `io` and `status` files of the process are kept open and read into a
+/// fixed buffer, so samples are taken without allocation. It is fast enough to sample a process
+/// every millisecond.
+///
+/// ```no_run
+/// use sysinfo::ProcessMonitor;
+///
+/// let mut monitor = ProcessMonitor::new(1337).expect("process not found");
+/// while let Some(sample) = monitor.sample() {
+///     println!("{}% {} bytes", sample.cpu_usage, sample.memory);
+///     std::thread::sleep(std::time::Duration::from_millis(1));
+/// }
+/// ```
+pub struct ProcessMonitor {
+    pid: Pid,
+    stat: File,
+    statm: File,
+    // Only readable by the owner of the process.
+    io: Option<File>,
+    status: Option<File>,
+    buffer: Box<[u8; 4096]>,
+    page_size: u64,
+    // Timestamp, user and system times (in clock ticks) of the previous sample.
+    previous: Option<(Instant, u64, u64)>,
+}
+
+impl ProcessMonitor {
+    /// Starts monitoring the process `pid`. Returns `None` if it doesn't exist.
+    pub fn new(pid: Pid) -> Option<ProcessMonitor> {
+        let open = |name: &str| File::open(format!("/proc/{}/{}", pid, name)).ok();
+
+        Some(ProcessMonitor {
+            pid,
+            stat: open("stat")?,
+            statm: open("statm")?,
+            io: open("io"),
+            status: open("status"),
+            buffer: Box::new([0; 4096]),
+            page_size: unsafe { sysconf(_SC_PAGESIZE) as u64 },
+            previous: None,
+        })
+    }
+
+    /// Returns the pid of the monitored process.
+    pub fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    /// Takes a new sample. Returns `None` if the process doesn't exist anymore.
+    pub fn sample(&mut self) -> Option<ProcessSample> {
+        let buffer = &mut self.buffer[..];
+
+        let data = read(&self.stat, buffer)?;
+        let timestamp = Instant::now();
+        // The process name is between parentheses and can contain spaces (or even invalid UTF-8),
+        // so we start after it.
+        let data = str::from_utf8(data.get(data.iter().rposition(|c| *c == b')')? + 2..)?).ok()?;
+        let mut parts = data.split(' ');
+        let status = ProcessStatus::from(parts.next()?.chars().next()?);
+        // After the status, `utime` is the 11th field, `stime` the 12th and `num_threads` the
+        // 17th.
+        let mut parts = parts.skip(10);
+        let utime = u64::from_str(parts.next()?).ok()?;
+        let stime = u64::from_str(parts.next()?).ok()?;
+        let thread_count = usize::from_str(parts.nth(4)?).ok()?;
+
+        let (cpu_usage, user_cpu_usage, system_cpu_usage) = match self.previous {
+            Some((previous, old_utime, old_stime)) => {
+                let elapsed = timestamp.duration_since(previous).as_secs_f32();
+                let usage = |ticks: u64| {
+                    if elapsed > 0. {
+                        ticks_to_duration(ticks).as_secs_f32() / elapsed * 100.
+                    } else {
+                        0.
+                    }
+                };
+                let user = usage(utime.saturating_sub(old_utime));
+                let system = usage(stime.saturating_sub(old_stime));
+                (user + system, user, system)
+            }
+            None => (0., 0., 0.),
+        };
+        self.previous = Some((timestamp, utime, stime));
+
+        let data = str::from_utf8(read(&self.statm, buffer)?).ok()?;
+        let mut parts = data.split_whitespace();
+        let virtual_memory = u64::from_str(parts.next()?).ok()? * self.page_size;
+        let memory = u64::from_str(parts.next()?).ok()? * self.page_size;
+
+        let (mut read_bytes, mut written_bytes) = (0, 0);
+        if let Some(data) = self
+            .io
+            .as_ref()
+            .and_then(|f| str::from_utf8(read(f, buffer)?).ok())
+        {
+            for line in data.lines() {
+                let field = if line.starts_with("read_bytes:") {
+                    &mut read_bytes
+                } else if line.starts_with("write_bytes:") {
+                    &mut written_bytes
+                } else {
+                    continue;
+                };
+                *field = parse_value(line);
+            }
+        }
+
+        let mut peak_memory = memory;
+        if let Some(data) = self.status.as_ref().and_then(|f| read(f, buffer)) {
+            // The process name is in this file too, so it might not be valid UTF-8.
+            let data = String::from_utf8_lossy(data);
+            if let Some(line) = data.lines().find(|l| l.starts_with("VmHWM:")) {
+                // The value is in KiB, though it says "kB".
+                peak_memory = peak_memory.max(parse_value(line) * 1_024);
+            }
+        }
+
+        Some(ProcessSample {
+            timestamp,
+            cpu_usage,
+            user_cpu_usage,
+            system_cpu_usage,
+            memory,
+            virtual_memory,
+            peak_memory,
+            read_bytes,
+            written_bytes,
+            thread_count,
+            status,
+        })
+    }
+}
+
+/// Reads `file` from its start into `buffer`. Returns `None` if the process is gone.
+fn read<'a>(file: &File, buffer: &'a mut [u8]) -> Option<&'a [u8]> {
+    match file.read_at(buffer, 0) {
+        Ok(0) | Err(_) => None,
+        Ok(n) => Some(&buffer[..n]),
+    }
+}
+
+/// Parses the value of "name: value [unit]" lines.
+fn parse_value(line: &str) -> u64 {
+    line.split_whitespace()
+        .nth(1)
+        .and_then(|v| u64::from_str(v).ok())
+        .unwrap_or(0)
+}
@@ -0,0 +1,197 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::sys::utils::get_all_data;
+use crate::NumaNode;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+const NODES_PATH: &str = "/sys/devices/system/node";
+
+/// Returns the identifier of a node from the name of its directory (`node0` for example).
+fn node_id(name: &str) -> Option<usize> {
+    name.strip_prefix("node")
+        .and_then(|id| usize::from_str(id).ok())
+}
+
+pub(crate) fn get_numa_nodes() -> Vec<NumaNode> {
+    let mut nodes = match fs::read_dir(NODES_PATH) {
+        Ok(dir) => dir
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let id = node_id(entry.file_name().to_str()?)?;
+                Some(get_numa_node(&entry.path(), id))
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => return Vec::new(),
+    };
+    nodes.sort_unstable_by_key(|node| node.id);
+    nodes
+}
+
+fn get_numa_node(path: &Path, id: usize) -> NumaNode {
+    let read = |file| get_all_data(path.join(file), 4_096).unwrap_or_default();
+    let mut node = NumaNode {
+        id,
+        cpus: parse_cpu_list(read("cpulist").trim()),
+        distances: read("distance")
+            .split_whitespace()
+            .filter_map(|d| u32::from_str(d).ok())
+            .collect(),
+        ..NumaNode::default()
+    };
+    parse_node_meminfo(&read("meminfo"), &mut node);
+    parse_numastat(&read("numastat"), &mut node);
+    node
+}
+
+/// Returns the node of the CPU named `name` (`cpu0` for example).
+pub(crate) fn cpu_node(name: &str) -> Option<usize> {
+    fs::read_dir(Path::new("/sys/devices/system/cpu").join(name))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .find_map(|entry| node_id(entry.file_name().to_str()?))
+}
+
+/// Parses a list of CPUs like `0-3,8,10-11`.
+pub(crate) fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+
+    for range in list.split(',') {
+        let mut bounds = range.splitn(2, '-').map(usize::from_str);
+        match (bounds.next(), bounds.next()) {
+            (Some(Ok(start)), Some(Ok(end))) => cpus.extend(start..=end),
+            (Some(Ok(cpu)), None) => cpus.push(cpu),
+            _ => {}
+        }
+    }
+    cpus
+}
+
+/// Parses the `meminfo` file of a node, whose lines look like `Node 0 MemTotal: 16318072 kB`.
+fn parse_node_meminfo(data: &str, node: &mut NumaNode) {
+    for line in data.lines() {
+        let mut parts = line.split_whitespace().skip(2);
+        let field = match parts.next() {
+            Some("MemTotal:") => &mut node.total_memory,
+            Some("MemFree:") => &mut node.free_memory,
+            Some("MemUsed:") => &mut node.used_memory,
+            _ => continue,
+        };
+        // The values are in KiB, though it says "kB".
+        if let Some(Ok(value)) = parts.next().map(u64::from_str) {
+            *field = value * 1_024;
+        }
+    }
+}
+
+fn parse_numastat(data: &str, node: &mut NumaNode) {
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let field = match parts.next() {
+            Some("numa_hit") => &mut node.numa_hit,
+            Some("numa_miss") => &mut node.numa_miss,
+            Some("numa_foreign") => &mut node.numa_foreign,
+            Some("interleave_hit") => &mut node.interleave_hit,
+            Some("local_node") => &mut node.local_node,
+            Some("other_node") => &mut node.other_node,
+            _ => continue,
+        };
+        if let Some(Ok(value)) = parts.next().map(u64::from_str) {
+            *field = value;
+        }
+    }
+}
+
+/// Parses `/proc/[pid]/numa_maps` and returns the memory (in bytes) on each node. Its lines look
+/// like:
+///
+/// ```text
+/// 7f1c2e200000 default file=/usr/lib/libc.so.6 mapped=40 mapmax=80 N0=24 N1=16 kernelpagesize_kB=4
+/// ```
+pub(crate) fn parse_numa_maps(data: &str) -> HashMap<usize, u64> {
+    let mut memory = HashMap::new();
+
+    for line in data.lines() {
+        let mut page_size = 4_096;
+        let mut pages = Vec::new();
+
+        for part in line.split_whitespace().skip(2) {
+            let mut iter = part.splitn(2, '=');
+            let (key, value) = match (iter.next(), iter.next().map(u64::from_str)) {
+                (Some(key), Some(Ok(value))) => (key, value),
+                _ => continue,
+            };
+            if key == "kernelpagesize_kB" {
+                page_size = value * 1_024;
+            } else if let Some(Ok(node)) = key.strip_prefix('N').map(usize::from_str) {
+                pages.push((node, value));
+            }
+        }
+        for (node, pages) in pages {
+            *memory.entry(node).or_insert(0) += pages * page_size;
+        }
+    }
+    memory
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cpu_list, parse_node_meminfo, parse_numa_maps, parse_numastat};
+    use crate::NumaNode;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("5"), vec![5]);
+        assert!(parse_cpu_list("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_node_files() {
+        let mut node = NumaNode::default();
+        parse_node_meminfo(
+            "Node 1 MemTotal:       16318072 kB
+Node 1 MemFree:         8000000 kB
+Node 1 MemUsed:         8318072 kB
+Node 1 Active:          1000 kB
+",
+            &mut node,
+        );
+        parse_numastat(
+            "numa_hit 1000
+numa_miss 20
+numa_foreign 30
+interleave_hit 40
+local_node 900
+other_node 120
+",
+            &mut node,
+        );
+        assert_eq!(node.total_memory, 16_318_072 * 1_024);
+        assert_eq!(node.free_memory, 8_000_000 * 1_024);
+        assert_eq!(node.used_memory, 8_318_072 * 1_024);
+        assert_eq!(node.numa_hit, 1_000);
+        assert_eq!(node.numa_miss, 20);
+        assert_eq!(node.numa_foreign, 30);
+        assert_eq!(node.interleave_hit, 40);
+        assert_eq!(node.local_node, 900);
+        assert_eq!(node.other_node, 120);
+    }
+
+    #[test]
+    fn test_parse_numa_maps() {
+        let memory = parse_numa_maps(
+            "55d0c8a00000 default file=/usr/bin/cat mapped=2 N0=2 kernelpagesize_kB=4
+7f1c2e200000 default anon=512 dirty=512 N0=500 N1=12 kernelpagesize_kB=4
+7f1c40000000 bind:1 huge anon=2 dirty=2 N1=2 kernelpagesize_kB=2048
+7ffd1a5e2000 default
+",
+        );
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory[&0], 502 * 4_096);
+        assert_eq!(memory[&1], 12 * 4_096 + 2 * 2_048 * 1_024);
+    }
+}
@@ -0,0 +1,145 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::sys::utils::get_all_data;
+use crate::OsRelease;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Reads `/etc/os-release`, falling back to `/usr/lib/os-release` as the specification says.
+pub(crate) fn get_os_release() -> Option<OsRelease> {
+    read_os_release(&[
+        Path::new("/etc/os-release"),
+        Path::new("/usr/lib/os-release"),
+    ])
+}
+
+/// Reads the first of `paths` which exists.
+pub(crate) fn read_os_release(paths: &[&Path]) -> Option<OsRelease> {
+    paths
+        .iter()
+        .find_map(|path| read_vars(path))
+        .map(to_os_release)
+}
+
+fn read_vars(path: &Path) -> Option<HashMap<String, String>> {
+    get_all_data(path, 4_096).ok().map(|data| parse_vars(&data))
+}
+
+/// Parses the `KEY=value` lines of an `os-release` file. Values can be quoted and escaped like
+/// in a shell, which is what this function undoes.
+pub(crate) fn parse_vars(data: &str) -> HashMap<String, String> {
+    data.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return None;
+            }
+            Some((key.to_owned(), unquote(value)))
+        })
+        .collect()
+}
+
+fn unquote(value: &str) -> String {
+    let mut ret = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    let mut quote = None;
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '\\') => ret.extend(chars.next()),
+            // In double quotes, a backslash only escapes the characters which have a meaning there.
+            (Some('"'), '\\') => match chars.next() {
+                Some(next @ '$') | Some(next @ '"') | Some(next @ '\\') | Some(next @ '`') => {
+                    ret.push(next)
+                }
+                Some(next) => {
+                    ret.push('\\');
+                    ret.push(next);
+                }
+                None => ret.push('\\'),
+            },
+            (_, c) => ret.push(c),
+        }
+    }
+    ret
+}
+
+fn to_os_release(mut vars: HashMap<String, String>) -> OsRelease {
+    let name = vars.remove("NAME").unwrap_or_else(|| "Linux".to_owned());
+
+    OsRelease {
+        id: vars.remove("ID").unwrap_or_else(|| "linux".to_owned()),
+        id_like: vars
+            .remove("ID_LIKE")
+            .map(|ids| ids.split_whitespace().map(str::to_owned).collect())
+            .unwrap_or_default(),
+        pretty_name: vars
+            .remove("PRETTY_NAME")
+            .unwrap_or_else(|| "Linux".to_owned()),
+        version: vars.remove("VERSION"),
+        version_id: vars.remove("VERSION_ID"),
+        version_codename: vars.remove("VERSION_CODENAME"),
+        build_id: vars.remove("BUILD_ID"),
+        variant_id: vars.remove("VARIANT_ID"),
+        home_url: vars.remove("HOME_URL"),
+        name,
+        others: vars,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_vars, to_os_release};
+
+    #[test]
+    fn test_parse_os_release() {
+        let os = to_os_release(parse_vars(
+            r#"# Comment
+NAME="Fedora Linux"
+VERSION="38 (Workstation Edition)"
+ID=fedora
+ID_LIKE="rhel centos"
+PRETTY_NAME='Fedora Linux 38 (Workstation Edition)'
+VARIANT_ID=workstation
+HOME_URL="https://fedoraproject.org/"
+ANSI_COLOR="0;38;2;60;110;180"
+LOGO=fedora-logo-icon
+QUOTED="a \"quoted\" \$value\\ with \`backticks\` and \n"
+UNQUOTED=some\ spaces
+invalid line
+"#,
+        ));
+
+        assert_eq!(os.name, "Fedora Linux");
+        assert_eq!(os.id, "fedora");
+        assert_eq!(os.id_like, vec!["rhel".to_owned(), "centos".to_owned()]);
+        assert_eq!(os.pretty_name, "Fedora Linux 38 (Workstation Edition)");
+        assert_eq!(os.version.as_deref(), Some("38 (Workstation Edition)"));
+        assert_eq!(os.version_id, None);
+        assert_eq!(os.variant_id.as_deref(), Some("workstation"));
+        assert_eq!(os.home_url.as_deref(), Some("https://fedoraproject.org/"));
+        assert_eq!(os.others.len(), 4);
+        assert_eq!(os.others["ANSI_COLOR"], "0;38;2;60;110;180");
+        assert_eq!(os.others["LOGO"], "fedora-logo-icon");
+        assert_eq!(
+            os.others["QUOTED"],
+            r#"a "quoted" $value\ with `backticks` and \n"#
+        );
+        assert_eq!(os.others["UNQUOTED"], "some spaces");
+    }
+
+    #[test]
+    fn test_os_release_defaults() {
+        let os = to_os_release(parse_vars(""));
+
+        assert_eq!(os.name, "Linux");
+        assert_eq!(os.id, "linux");
+        assert_eq!(os.pretty_name, "Linux");
+        assert!(os.id_like.is_empty());
+    }
+}
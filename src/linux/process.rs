@@ -2,18 +2,27 @@
 
 use std::cell::UnsafeCell;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ffi::OsStr;
 use std::fmt;
 use std::fs::{self, File};
 use std::io::Read;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 
 use libc::{gid_t, kill, sysconf, uid_t, _SC_CLK_TCK};
 
-use crate::sys::system::REMAINING_FILES;
+use crate::sys::numa;
+use crate::sys::system::{boot_time, precise_boot_time, REMAINING_FILES};
 use crate::sys::utils::{get_all_data, get_all_data_from_file, realpath};
 use crate::utils::into_iter;
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{
+    DiskUsage, Pid, ProcessExt, ProcessRefreshKind, ProcessStatus, Signal, StaleFile,
+    StaleFileReason, Uid,
+};
 
 #[doc(hidden)]
 impl From<u32> for ProcessStatus {
@@ -76,15 +85,19 @@ pub struct Process {
     pub(crate) environ: Vec<String>,
     pub(crate) cwd: PathBuf,
     pub(crate) root: PathBuf,
+    pub(crate) cgroup: PathBuf,
     pub(crate) memory: u64,
     pub(crate) virtual_memory: u64,
     utime: u64,
     stime: u64,
     old_utime: u64,
     old_stime: u64,
-    start_time: u64,
-    updated: bool,
+    /// Start time of the process, since the UNIX epoch.
+    start_time: Duration,
+    pub(crate) updated: bool,
     cpu_usage: f32,
+    user_cpu_usage: f32,
+    system_cpu_usage: f32,
     /// User id of the process owner.
     pub uid: uid_t,
     /// Group id of the process owner.
@@ -97,6 +110,10 @@ pub struct Process {
     old_written_bytes: u64,
     read_bytes: u64,
     written_bytes: u64,
+    /// Information which doesn't change and has already been retrieved.
+    loaded_info: ProcessRefreshKind,
+    is_kernel_thread: bool,
+    thread_count: usize,
 }
 
 impl ProcessExt for Process {
@@ -110,15 +127,18 @@ impl ProcessExt for Process {
             exe: PathBuf::new(),
             cwd: PathBuf::new(),
             root: PathBuf::new(),
+            cgroup: PathBuf::new(),
             memory: 0,
             virtual_memory: 0,
             cpu_usage: 0.,
+            user_cpu_usage: 0.,
+            system_cpu_usage: 0.,
             utime: 0,
             stime: 0,
             old_utime: 0,
             old_stime: 0,
             updated: true,
-            start_time,
+            start_time: Duration::from_secs(start_time),
             uid: 0,
             gid: 0,
             status: ProcessStatus::Unknown(0),
@@ -132,6 +152,9 @@ impl ProcessExt for Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            loaded_info: ProcessRefreshKind::new(),
+            is_kernel_thread: false,
+            thread_count: 0,
         }
     }
 
@@ -173,6 +196,21 @@ impl ProcessExt for Process {
         unsafe { kill(self.pid, c_signal) == 0 }
     }
 
+    fn stale_files(&self) -> Vec<StaleFile> {
+        get_stale_files(&Path::new("/proc").join(self.pid.to_string()))
+    }
+
+    fn numa_memory(&self) -> HashMap<usize, u64> {
+        get_all_data(
+            Path::new("/proc")
+                .join(self.pid.to_string())
+                .join("numa_maps"),
+            16_384,
+        )
+        .map(|data| numa::parse_numa_maps(&data))
+        .unwrap_or_default()
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -218,13 +256,55 @@ impl ProcessExt for Process {
     }
 
     fn start_time(&self) -> u64 {
-        self.start_time
+        self.start_time.as_secs()
+    }
+
+    fn started_at(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + self.start_time
+    }
+
+    fn run_time(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.started_at())
+            .unwrap_or_default()
     }
 
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn user_cpu_usage(&self) -> f32 {
+        self.user_cpu_usage
+    }
+
+    fn system_cpu_usage(&self) -> f32 {
+        self.system_cpu_usage
+    }
+
+    fn total_cpu_time(&self) -> Duration {
+        ticks_to_duration(self.utime.saturating_add(self.stime))
+    }
+
+    fn thread_count(&self) -> usize {
+        self.thread_count
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        if self.loaded_info.user() {
+            Some(Uid(self.uid))
+        } else {
+            None
+        }
+    }
+
+    fn cgroup(&self) -> &Path {
+        &self.cgroup
+    }
+
+    fn is_kernel_thread(&self) -> bool {
+        self.is_kernel_thread
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             written_bytes: self.written_bytes - self.old_written_bytes,
@@ -253,12 +333,30 @@ pub fn compute_cpu_usage(p: &mut Process, total_time: f32, max_value: f32) {
 
     // We use `max_value` to ensure that the process CPU usage will never get bigger than:
     // `"number of CPUs" * 100.`
-    p.cpu_usage = ((p.utime.saturating_sub(p.old_utime) + p.stime.saturating_sub(p.old_stime))
-        as f32
-        / total_time
-        * 100.)
-        .min(max_value);
-    p.updated = false;
+    p.user_cpu_usage =
+        (p.utime.saturating_sub(p.old_utime) as f32 / total_time * 100.).min(max_value);
+    p.system_cpu_usage =
+        (p.stime.saturating_sub(p.old_stime) as f32 / total_time * 100.).min(max_value);
+    p.cpu_usage = (p.user_cpu_usage + p.system_cpu_usage).min(max_value);
+}
+
+/// Returns the number of clock ticks per second (`USER_HZ`), used in `/proc/[pid]/stat`.
+pub(crate) fn clock_ticks() -> u64 {
+    static CLOCK_TICKS: once_cell::sync::Lazy<u64> = once_cell::sync::Lazy::new(|| {
+        match unsafe { sysconf(_SC_CLK_TCK) } {
+            x if x > 0 => x as u64,
+            // Most linux systems use 100 so it's a reasonable fallback.
+            _ => 100,
+        }
+    });
+    *CLOCK_TICKS
+}
+
+/// Converts a number of clock ticks into a `Duration`, keeping the sub-second part.
+pub(crate) fn ticks_to_duration(ticks: u64) -> Duration {
+    let clock_ticks = clock_ticks();
+    Duration::from_secs(ticks / clock_ticks)
+        + Duration::from_nanos((ticks % clock_ticks) * 1_000_000_000 / clock_ticks)
 }
 
 pub fn set_time(p: &mut Process, utime: u64, stime: u64) {
@@ -266,7 +364,6 @@ pub fn set_time(p: &mut Process, utime: u64, stime: u64) {
     p.old_stime = p.stime;
     p.utime = utime;
     p.stime = stime;
-    p.updated = true;
 }
 
 pub fn has_been_updated(p: &Process) -> bool {
@@ -320,13 +417,64 @@ impl<'a, T> Wrap<'a, T> {
 unsafe impl<'a, T> Send for Wrap<'a, T> {}
 unsafe impl<'a, T> Sync for Wrap<'a, T> {}
 
+/// Builds the process `pid` without needing a `System`. Its CPU usage isn't computed.
+pub(crate) fn current_process(pid: Pid) -> Option<Process> {
+    let mut proc_list = Process::new(0, None, 0);
+
+    match _get_process_data(
+        &Path::new("/proc").join(pid.to_string()),
+        &mut proc_list,
+        unsafe { sysconf(libc::_SC_PAGESIZE) as u64 },
+        0,
+        precise_boot_time(boot_time),
+        ProcessRefreshKind::everything().without_threads(),
+    ) {
+        Ok((Some(p), _)) => Some(p),
+        _ => None,
+    }
+}
+
+/// Returns the children of `pid`, listed by the `children` file of each of its threads. It
+/// returns `None` if the kernel doesn't provide this file (`CONFIG_PROC_CHILDREN` not set).
+pub(crate) fn get_children(pid: Pid) -> Option<Vec<Pid>> {
+    // The main thread of the current process always exists, so it tells if the file is there.
+    let self_children = format!("/proc/self/task/{}/children", std::process::id());
+    if !Path::new(&self_children).exists() {
+        return None;
+    }
+    let mut children = Vec::new();
+    // If the process doesn't exist anymore, it has no children.
+    if let Ok(tasks) = fs::read_dir(Path::new("/proc").join(pid.to_string()).join("task")) {
+        for task in tasks.flatten() {
+            if let Ok(data) = get_all_data(task.path().join("children"), 1_024) {
+                children.extend(
+                    data.split_whitespace()
+                        .filter_map(|c| Pid::from_str(c).ok()),
+                );
+            }
+        }
+    }
+    Some(children)
+}
+
+/// Kernel threads are flagged with `PF_KTHREAD` (the ninth field of the `stat` file). If the
+/// flags cannot be read, we fallback to check if it is `kthreadd` or one of its children.
+fn is_kernel_thread(parts: &[&str]) -> bool {
+    const PF_KTHREAD: u64 = 0x0020_0000;
+
+    match u64::from_str(parts[8]) {
+        Ok(flags) => flags & PF_KTHREAD != 0,
+        Err(_) => parts[0] == "2" || parts[3] == "2",
+    }
+}
+
 pub(crate) fn _get_process_data(
     path: &Path,
     proc_list: &mut Process,
-    page_size_kb: u64,
+    page_size: u64,
     pid: Pid,
-    uptime: u64,
-    now: u64,
+    boot_time: Duration,
+    refresh_kind: ProcessRefreshKind,
 ) -> Result<(Option<Process>, Pid), ()> {
     let nb = match path.file_name().and_then(|x| x.to_str()).map(Pid::from_str) {
         Some(Ok(nb)) if nb != pid => nb,
@@ -342,6 +490,17 @@ pub(crate) fn _get_process_data(
     };
     let parent_memory = proc_list.memory;
     let parent_virtual_memory = proc_list.virtual_memory;
+    // The entry isn't removed here since this function can be called from multiple threads on the
+    // same `proc_list`: it's removed by the caller because it isn't marked as updated.
+    if !refresh_kind.kernel_threads()
+        && proc_list
+            .tasks
+            .get(&nb)
+            .map(|p| p.is_kernel_thread)
+            .unwrap_or(false)
+    {
+        return Err(());
+    }
     if let Some(ref mut entry) = proc_list.tasks.get_mut(&nb) {
         let data = if let Some(ref mut f) = entry.stat_file {
             get_all_data_from_file(f, 1024).map_err(|_| ())?
@@ -355,18 +514,23 @@ pub(crate) fn _get_process_data(
         };
         let parts = parse_stat_file(&data)?;
         get_status(entry, parts[2]);
+        entry.thread_count = usize::from_str(parts[19]).unwrap_or(0);
+        entry.updated = true;
+        update_static_info(entry, path, refresh_kind);
         update_time_and_memory(
             path,
             entry,
             &parts,
-            page_size_kb,
+            page_size,
             parent_memory,
             parent_virtual_memory,
             nb,
-            uptime,
-            now,
+            boot_time,
+            refresh_kind,
         );
-        update_process_disk_activity(entry, path);
+        if refresh_kind.disk_usage() {
+            update_process_disk_activity(entry, path);
+        }
         return Ok((None, nb));
     }
 
@@ -379,6 +543,11 @@ pub(crate) fn _get_process_data(
     let parts = parse_stat_file(&data)?;
     let name = parts[1];
 
+    let is_kernel_thread = is_kernel_thread(&parts);
+    if is_kernel_thread && !refresh_kind.kernel_threads() {
+        return Err(());
+    }
+
     let parent_pid = if proc_list.pid != 0 {
         Some(proc_list.pid)
     } else {
@@ -388,22 +557,14 @@ pub(crate) fn _get_process_data(
         }
     };
 
-    let clock_cycle = unsafe { sysconf(_SC_CLK_TCK) } as u64;
-    let since_boot = u64::from_str(parts[21]).unwrap_or(0) / clock_cycle;
-    let start_time = now.saturating_sub(uptime.saturating_sub(since_boot));
-    let mut p = Process::new(nb, parent_pid, start_time);
+    let mut p = Process::new(nb, parent_pid, 0);
+    // The start time is expressed in clock ticks since the system boot.
+    p.start_time = boot_time + ticks_to_duration(u64::from_str(parts[21]).unwrap_or(0));
 
     p.stat_file = stat_file;
+    p.is_kernel_thread = is_kernel_thread;
     get_status(&mut p, parts[2]);
-
-    tmp.pop();
-    tmp.push("status");
-    if let Ok(data) = get_all_data(&tmp, 16_385) {
-        if let Some((uid, gid)) = _get_uid_and_gid(data) {
-            p.uid = uid;
-            p.gid = gid;
-        }
-    }
+    p.thread_count = usize::from_str(parts[19]).unwrap_or(0);
 
     if proc_list.pid != 0 {
         // If we're getting information for a child, no need to get those info since we
@@ -414,16 +575,60 @@ pub(crate) fn _get_process_data(
         p.exe = proc_list.exe.clone();
         p.cwd = proc_list.cwd.clone();
         p.root = proc_list.root.clone();
+        p.cgroup = proc_list.cgroup.clone();
+        // The user information is specific to each task so it isn't retrieved from the parent.
+        p.loaded_info = proc_list.loaded_info.without_user();
     } else {
         p.name = name.into();
+    }
+    update_static_info(&mut p, path, refresh_kind);
+
+    update_time_and_memory(
+        path,
+        &mut p,
+        &parts,
+        page_size,
+        proc_list.memory,
+        proc_list.virtual_memory,
+        nb,
+        boot_time,
+        refresh_kind,
+    );
+    if refresh_kind.disk_usage() {
+        update_process_disk_activity(&mut p, path);
+    }
+    Ok((Some(p), nb))
+}
+
+// Retrieves the information which doesn't change during the process' life if it is asked for
+// and hasn't been retrieved yet.
+fn update_static_info(p: &mut Process, path: &Path, refresh_kind: ProcessRefreshKind) {
+    let mut tmp = PathBuf::from(path);
+
+    if refresh_kind.user() && !p.loaded_info.user() {
+        tmp.push("status");
+        if let Ok(data) = get_all_data(&tmp, 16_385) {
+            if let Some((uid, gid)) = _get_uid_and_gid(data) {
+                p.uid = uid;
+                p.gid = gid;
+            }
+        }
         tmp.pop();
+        p.loaded_info = p.loaded_info.with_user();
+    }
+    if refresh_kind.cmd() && !p.loaded_info.cmd() {
         tmp.push("cmdline");
         p.cmd = copy_from_file(&tmp);
         tmp.pop();
+        p.loaded_info = p.loaded_info.with_cmd();
+    }
+    if refresh_kind.exe() && !p.loaded_info.exe() {
         tmp.push("exe");
         match tmp.read_link() {
             Ok(exe_path) => {
-                p.exe = exe_path;
+                p.exe = strip_deleted_suffix(&exe_path)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(exe_path);
             }
             Err(_) => {
                 p.exe = if let Some(cmd) = p.cmd.get(0) {
@@ -434,29 +639,157 @@ pub(crate) fn _get_process_data(
             }
         }
         tmp.pop();
+        p.loaded_info = p.loaded_info.with_exe();
+    }
+    if refresh_kind.environ() && !p.loaded_info.environ() {
         tmp.push("environ");
         p.environ = copy_from_file(&tmp);
         tmp.pop();
+        p.loaded_info = p.loaded_info.with_environ();
+    }
+    if refresh_kind.cwd() && !p.loaded_info.cwd() {
         tmp.push("cwd");
         p.cwd = realpath(&tmp);
         tmp.pop();
+        p.loaded_info = p.loaded_info.with_cwd();
+    }
+    if refresh_kind.root() && !p.loaded_info.root() {
         tmp.push("root");
         p.root = realpath(&tmp);
+        tmp.pop();
+        p.loaded_info = p.loaded_info.with_root();
+    }
+    if refresh_kind.cgroup() && !p.loaded_info.cgroup() {
+        tmp.push("cgroup");
+        if let Ok(data) = get_all_data(&tmp, 16_385) {
+            p.cgroup = parse_cgroup_file(&data);
+        }
+        p.loaded_info = p.loaded_info.with_cgroup();
     }
+}
 
-    update_time_and_memory(
-        path,
-        &mut p,
-        &parts,
-        page_size_kb,
-        proc_list.memory,
-        proc_list.virtual_memory,
-        nb,
-        uptime,
-        now,
-    );
-    update_process_disk_activity(&mut p, path);
-    Ok((Some(p), nb))
+/// When the target of a link in `/proc` has been deleted, " (deleted)" is appended to its path.
+/// Returns the path without this suffix if it is present.
+fn strip_deleted_suffix(path: &Path) -> Option<&Path> {
+    path.as_os_str()
+        .as_bytes()
+        .strip_suffix(b" (deleted)")
+        .map(|p| Path::new(OsStr::from_bytes(p)))
+}
+
+/// Checks if the file mapped from the `dev` device with the given `inode` still exists on disk.
+/// The file is looked up from the `root` directory of the process, so it works for processes
+/// running in another mount namespace (in a container for example).
+fn stale_file(root: &Path, path: &Path, dev: u64, inode: u64) -> Option<StaleFile> {
+    // When a file is upgraded, the new version is usually renamed over the old one, so the old
+    // one is flagged as deleted by the kernel.
+    let (path, deleted) = match strip_deleted_suffix(path) {
+        Some(p) => (p, true),
+        None => (path, false),
+    };
+    let reason = match fs::metadata(root.join(path.strip_prefix("/").unwrap_or(path))) {
+        Ok(m) if deleted || (m.dev() == dev && m.ino() != inode) => StaleFileReason::Replaced,
+        // On overlay filesystems, the mapped file is the underlying one so it has a different
+        // device and we cannot compare inodes.
+        Ok(_) => return None,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => StaleFileReason::Deleted,
+        Err(_) if deleted => StaleFileReason::Deleted,
+        Err(_) => return None,
+    };
+    Some(StaleFile {
+        path: path.to_path_buf(),
+        reason,
+    })
+}
+
+/// Converts a "major:minor" device (in hexadecimal) into a `dev_t`, like `makedev` does.
+fn parse_dev(dev: &str) -> Option<u64> {
+    let mut parts = dev.splitn(2, ':');
+    let major = u64::from_str_radix(parts.next()?, 16).ok()?;
+    let minor = u64::from_str_radix(parts.next()?, 16).ok()?;
+    Some(
+        ((major & 0xffff_f000) << 32)
+            | ((major & 0xfff) << 8)
+            | ((minor & 0xffff_ff00) << 12)
+            | (minor & 0xff),
+    )
+}
+
+/// Each line of the `maps` file has the "address perms offset dev inode pathname" format. It
+/// returns the device, the inode and the path of the lines mapping a file.
+fn parse_maps_line(line: &[u8]) -> Option<(u64, u64, &Path)> {
+    let mut parts = line.splitn(6, |c| *c == b' ').skip(3);
+    let dev = parse_dev(std::str::from_utf8(parts.next()?).ok()?)?;
+    let inode = u64::from_str(std::str::from_utf8(parts.next()?).ok()?).ok()?;
+    let file = parts.next()?;
+    // The path is aligned with spaces.
+    let start = file.iter().position(|c| *c != b' ').unwrap_or(file.len());
+    let file = Path::new(OsStr::from_bytes(&file[start..]));
+    if inode != 0 && file.starts_with("/") {
+        Some((dev, inode, file))
+    } else {
+        None
+    }
+}
+
+fn get_stale_files(path: &Path) -> Vec<StaleFile> {
+    let mut stale_files = Vec::new();
+    let mut checked = HashSet::new();
+    let root = path.join("root");
+    let mut tmp = path.join("exe");
+
+    // `metadata` follows the link, so we get the executable which is running, even if it has
+    // been deleted.
+    if let (Ok(exe), Ok(m)) = (tmp.read_link(), fs::metadata(&tmp)) {
+        if let Some(f) = stale_file(&root, &exe, m.dev(), m.ino()) {
+            stale_files.push(f);
+        }
+        checked.insert(exe);
+    }
+    tmp.set_file_name("maps");
+    // Paths aren't necessarily valid UTF-8, so the file is read as bytes.
+    let data = match fs::read(&tmp) {
+        Ok(data) => data,
+        Err(_) => return stale_files,
+    };
+    for line in data.split(|c| *c == b'\n') {
+        let (dev, inode, file) = match parse_maps_line(line) {
+            Some(x) => x,
+            None => continue,
+        };
+        let bytes = file.as_os_str().as_bytes();
+        if bytes.starts_with(b"/dev/")
+            || bytes.starts_with(b"/memfd:")
+            || bytes.starts_with(b"/SYSV")
+            || !checked.insert(file.to_path_buf())
+        {
+            continue;
+        }
+        if let Some(f) = stale_file(&root, file, dev, inode) {
+            stale_files.push(f);
+        }
+    }
+    stale_files
+}
+
+/// Each line of the `cgroup` file has the "hierarchy-ID:controller-list:cgroup-path" format.
+/// The cgroup v2 hierarchy always has the ID 0 and an empty controller list.
+pub(crate) fn parse_cgroup_file(data: &str) -> PathBuf {
+    let mut fallback = None;
+
+    for line in data.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (id, controllers, path) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(id), Some(controllers), Some(path)) => (id, controllers, path),
+            _ => continue,
+        };
+        if id == "0" && controllers.is_empty() {
+            return PathBuf::from(path);
+        } else if controllers == "name=systemd" || fallback.is_none() {
+            fallback = Some(path);
+        }
+    }
+    fallback.map(PathBuf::from).unwrap_or_default()
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -464,16 +797,16 @@ fn update_time_and_memory(
     path: &Path,
     entry: &mut Process,
     parts: &[&str],
-    page_size_kb: u64,
+    page_size: u64,
     parent_memory: u64,
     parent_virtual_memory: u64,
     pid: Pid,
-    uptime: u64,
-    now: u64,
+    boot_time: Duration,
+    refresh_kind: ProcessRefreshKind,
 ) {
-    {
+    if refresh_kind.memory() {
         // rss
-        entry.memory = u64::from_str(parts[23]).unwrap_or(0) * page_size_kb;
+        entry.memory = u64::from_str(parts[23]).unwrap_or(0) * page_size;
         if entry.memory >= parent_memory {
             entry.memory -= parent_memory;
         }
@@ -482,22 +815,33 @@ fn update_time_and_memory(
         if entry.virtual_memory >= parent_virtual_memory {
             entry.virtual_memory -= parent_virtual_memory;
         }
+    }
+    if refresh_kind.cpu() {
         set_time(
             entry,
             u64::from_str(parts[13]).unwrap_or(0),
             u64::from_str(parts[14]).unwrap_or(0),
         );
     }
-    refresh_procs(entry, &path.join("task"), page_size_kb, pid, uptime, now);
+    if refresh_kind.threads() {
+        refresh_procs(
+            entry,
+            &path.join("task"),
+            page_size,
+            pid,
+            boot_time,
+            refresh_kind,
+        );
+    }
 }
 
 pub(crate) fn refresh_procs(
     proc_list: &mut Process,
     path: &Path,
-    page_size_kb: u64,
+    page_size: u64,
     pid: Pid,
-    uptime: u64,
-    now: u64,
+    boot_time: Duration,
+    refresh_kind: ProcessRefreshKind,
 ) -> bool {
     if let Ok(d) = fs::read_dir(path) {
         let folders = d
@@ -526,10 +870,10 @@ pub(crate) fn refresh_procs(
                     if let Ok((p, _)) = _get_process_data(
                         e.as_path(),
                         proc_list.get(),
-                        page_size_kb,
+                        page_size,
                         pid,
-                        uptime,
-                        now,
+                        boot_time,
+                        refresh_kind,
                     ) {
                         p
                     } else {
@@ -542,9 +886,14 @@ pub(crate) fn refresh_procs(
             let new_tasks = folders
                 .iter()
                 .filter_map(|e| {
-                    if let Ok((p, pid)) =
-                        _get_process_data(e.as_path(), proc_list, page_size_kb, pid, uptime, now)
-                    {
+                    if let Ok((p, pid)) = _get_process_data(
+                        e.as_path(),
+                        proc_list,
+                        page_size,
+                        pid,
+                        boot_time,
+                        refresh_kind,
+                    ) {
                         updated_pids.push(pid);
                         p
                     } else {
@@ -675,3 +1024,57 @@ fn parse_stat_file(data: &str) -> Result<Vec<&str>, ()> {
     }
     Ok(parts)
 }
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cgroup_file, parse_maps_line};
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_cgroup_file() {
+        assert_eq!(
+            parse_cgroup_file("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            Path::new("/user.slice/user-1000.slice/session-2.scope"),
+        );
+        assert_eq!(
+            parse_cgroup_file(
+                "12:cpu,cpuacct:/system.slice/foo.service\n\
+                 1:name=systemd:/system.slice/bar.service\n"
+            ),
+            Path::new("/system.slice/bar.service"),
+        );
+        assert_eq!(
+            parse_cgroup_file(
+                "1:name=systemd:/system.slice/bar.service\n\
+                 0::/system.slice/foo.service\n"
+            ),
+            Path::new("/system.slice/foo.service"),
+        );
+        assert_eq!(parse_cgroup_file(""), Path::new(""));
+    }
+
+    #[test]
+    fn test_parse_maps_line() {
+        assert_eq!(
+            parse_maps_line(
+                b"7f0e0b000000-7f0e0b022000 r--p 00000000 fd:01 1234            /usr/lib/libc.so.6"
+            ),
+            Some((0xfd01, 1234, Path::new("/usr/lib/libc.so.6"))),
+        );
+        // Paths aren't necessarily valid UTF-8.
+        assert_eq!(
+            parse_maps_line(b"7f0e0b000000-7f0e0b022000 r--p 00000000 fd:01 1234 /tmp/\xff.so"),
+            Some((0xfd01, 1234, Path::new(OsStr::from_bytes(b"/tmp/\xff.so")))),
+        );
+        assert_eq!(
+            parse_maps_line(b"7ffd5d6e6000-7ffd5d707000 rw-p 00000000 00:00 0 [stack]"),
+            None,
+        );
+        assert_eq!(
+            parse_maps_line(b"7f0e0b000000-7f0e0b022000 rw-p 00000000 00:00 0 "),
+            None,
+        );
+    }
+}
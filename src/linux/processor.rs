@@ -123,6 +123,7 @@ pub struct Processor {
     pub(crate) frequency: u64,
     pub(crate) vendor_id: String,
     pub(crate) brand: String,
+    pub(crate) numa_node: Option<usize>,
 }
 
 impl Processor {
@@ -154,6 +155,7 @@ impl Processor {
             frequency,
             vendor_id,
             brand,
+            numa_node: None,
         }
     }
 
@@ -215,6 +217,10 @@ impl ProcessorExt for Processor {
     fn brand(&self) -> &str {
         &self.brand
     }
+
+    fn numa_node(&self) -> Option<usize> {
+        self.numa_node
+    }
 }
 
 pub fn get_raw_times(p: &Processor) -> (u64, u64) {
@@ -0,0 +1,158 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::sys::utils::get_all_data;
+use crate::{Swap, SwapType, ZramStats};
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub(crate) fn get_swaps() -> Vec<Swap> {
+    match get_all_data("/proc/swaps", 4_096) {
+        Ok(data) => parse_swaps(&data, |name| {
+            get_all_data(Path::new("/sys/block").join(name).join("mm_stat"), 256)
+                .ok()
+                .and_then(|data| parse_mm_stat(&data))
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Parses `/proc/swaps`, which looks like:
+///
+/// ```text
+/// Filename                                Type            Size            Used            Priority
+/// /dev/zram0                              partition       4194300         0               100
+/// /swapfile                               file            2097148         1024            -2
+/// ```
+///
+/// `zram_stats` is called with the name of the zram devices.
+fn parse_swaps<F: Fn(&str) -> Option<ZramStats>>(data: &str, zram_stats: F) -> Vec<Swap> {
+    data.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let path = PathBuf::from(unescape(parts.next()?));
+            let type_ = match parts.next()? {
+                "partition" => SwapType::Partition,
+                "file" => SwapType::File,
+                _ => SwapType::Unknown,
+            };
+            // The sizes are in KiB.
+            let size = u64::from_str(parts.next()?).ok()? * 1_024;
+            let used = u64::from_str(parts.next()?).ok()? * 1_024;
+            let priority = i32::from_str(parts.next()?).ok()?;
+            let zram = match path.strip_prefix("/dev").ok().and_then(|p| p.to_str()) {
+                Some(name) if type_ == SwapType::Partition && name.starts_with("zram") => {
+                    zram_stats(name)
+                }
+                _ => None,
+            };
+
+            Some(Swap {
+                path,
+                type_,
+                size,
+                used,
+                priority,
+                zram,
+            })
+        })
+        .collect()
+}
+
+/// The kernel escapes spaces, tabs, new lines and backslashes in paths with their octal value
+/// (`\040` for a space).
+fn unescape(path: &str) -> String {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(pos) = rest.find('\\') {
+        unescaped.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        match rest
+            .get(1..4)
+            .and_then(|code| u8::from_str_radix(code, 8).ok())
+        {
+            Some(c) => {
+                unescaped.push(c as char);
+                rest = &rest[4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// Parses `/sys/block/zramN/mm_stat`. The first three values are the original size, the
+/// compressed size and the total memory used, all in bytes.
+fn parse_mm_stat(data: &str) -> Option<ZramStats> {
+    let mut parts = data.split_whitespace().map(u64::from_str);
+
+    Some(ZramStats {
+        original_size: parts.next()?.ok()?,
+        compressed_size: parts.next()?.ok()?,
+        memory_used: parts.next()?.ok()?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_mm_stat, parse_swaps, unescape};
+    use crate::{SwapType, ZramStats};
+
+    #[test]
+    fn test_parse_swaps() {
+        let zram = ZramStats {
+            original_size: 3_000,
+            compressed_size: 1_000,
+            memory_used: 1_200,
+        };
+        let swaps = parse_swaps(
+            "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority
+/dev/zram0                              partition\t4194300\t\t1024\t\t100
+/my\\040swap                             file\t\t2097148\t\t0\t\t-2
+/dev/sda2                               partition\t1024\t\t0\t\t-3
+",
+            |name| {
+                assert_eq!(name, "zram0");
+                Some(zram)
+            },
+        );
+        assert_eq!(swaps.len(), 3);
+        assert_eq!(swaps[0].path.to_str(), Some("/dev/zram0"));
+        assert_eq!(swaps[0].type_, SwapType::Partition);
+        assert_eq!(swaps[0].size, 4_194_300 * 1_024);
+        assert_eq!(swaps[0].used, 1_048_576);
+        assert_eq!(swaps[0].priority, 100);
+        assert_eq!(swaps[0].zram, Some(zram));
+        assert_eq!(swaps[1].path.to_str(), Some("/my swap"));
+        assert_eq!(swaps[1].type_, SwapType::File);
+        assert_eq!(swaps[1].priority, -2);
+        assert_eq!(swaps[1].zram, None);
+        assert_eq!(swaps[2].zram, None);
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("/swapfile"), "/swapfile");
+        assert_eq!(unescape("/a\\040b\\011c\\134"), "/a b\tc\\");
+        assert_eq!(unescape("/a\\"), "/a\\");
+    }
+
+    #[test]
+    fn test_parse_mm_stat() {
+        let stats = parse_mm_stat(
+            "  8192000  2048000  2400000        0  2400000     1024        0        0\n",
+        )
+        .expect("failed to parse mm_stat");
+        assert_eq!(stats.original_size, 8_192_000);
+        assert_eq!(stats.compressed_size, 2_048_000);
+        assert_eq!(stats.memory_used, 2_400_000);
+        assert_eq!(stats.compression_ratio(), 4.);
+        assert!(parse_mm_stat("").is_none());
+    }
+}
@@ -1,11 +1,25 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use crate::query::group_processes;
+use crate::sys::cgroup;
 use crate::sys::component::{self, Component};
 use crate::sys::disk;
+use crate::sys::dmi;
+use crate::sys::numa;
+#[cfg(not(target_os = "android"))]
+use crate::sys::os_release;
 use crate::sys::process::*;
 use crate::sys::processor::*;
+use crate::sys::swap;
 use crate::sys::utils::get_all_data;
-use crate::{Disk, LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, User};
+use crate::sys::utmp;
+use crate::sys::virtualization;
+use crate::{
+    Cgroups, Disk, HardwareInfo, KernelVersion, LoadAvg, LoginRecord, MemoryInfo, Networks,
+    NumaNode, OsRelease, Pid, Pressure, PressureStats, ProcessExt, ProcessRefreshKind, RefreshKind,
+    ResourcePressure, Session, Swap, SystemExt, SystemdUnit, User, Virtualization, VmCounter,
+    VmStats,
+};
 
 use libc::{self, c_char, sysconf, _SC_HOST_NAME_MAX, _SC_PAGESIZE};
 use std::collections::HashMap;
@@ -14,7 +28,7 @@ use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
 // This whole thing is to prevent having too many files open at once. It could be problematic
 // for processes using a lot of files and using sysinfo at the same time.
@@ -68,7 +82,7 @@ macro_rules! to_str {
     };
 }
 
-fn boot_time() -> u64 {
+pub(crate) fn boot_time() -> u64 {
     if let Ok(f) = File::open("/proc/stat") {
         let buf = BufReader::new(f);
         let line = buf
@@ -98,25 +112,49 @@ fn boot_time() -> u64 {
     }
 }
 
+// The boot time from `/proc/stat` is truncated to the second. To be able to compute processes'
+// start time with sub-second precision, we compute it from the difference between the real time
+// and the time elapsed since boot. `fallback` is only called if the clocks cannot be read.
+pub(crate) fn precise_boot_time<F: FnOnce() -> u64>(fallback: F) -> Duration {
+    let mut now = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    let mut up = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    unsafe {
+        if libc::clock_gettime(libc::CLOCK_REALTIME, &mut now) != 0
+            || libc::clock_gettime(libc::CLOCK_BOOTTIME, &mut up) != 0
+        {
+            sysinfo_debug!("clock_gettime failed: falling back to `btime`...");
+            return Duration::from_secs(fallback());
+        }
+    }
+    let now = Duration::new(now.tv_sec as u64, now.tv_nsec as u32);
+    let up = Duration::new(up.tv_sec as u64, up.tv_nsec as u32);
+    now.checked_sub(up)
+        .unwrap_or_else(|| Duration::from_secs(fallback()))
+}
+
 #[doc = include_str!("../../md_doc/system.md")]
 pub struct System {
     process_list: Process,
-    mem_total: u64,
-    mem_free: u64,
-    mem_available: u64,
-    mem_buffers: u64,
-    mem_page_cache: u64,
-    mem_slab_reclaimable: u64,
-    swap_total: u64,
-    swap_free: u64,
+    memory_info: MemoryInfo,
+    vm_stats: VmStats,
+    // When `vm_stats` was refreshed for the last time.
+    vm_stats_refresh: Option<Instant>,
     global_processor: Processor,
     processors: Vec<Processor>,
-    page_size_kb: u64,
+    page_size: u64,
     components: Vec<Component>,
     disks: Vec<Disk>,
     networks: Networks,
+    cgroups: Cgroups,
     users: Vec<User>,
     boot_time: u64,
+    precise_boot_time: Duration,
 }
 
 impl System {
@@ -129,20 +167,24 @@ impl System {
         self.processors.len() as f32 * 100.
     }
 
-    fn clear_procs(&mut self) {
-        self.refresh_processors(true);
+    fn clear_procs(&mut self, refresh_kind: ProcessRefreshKind) {
+        let (total_time, compute_cpu, max_value) = if refresh_kind.cpu() {
+            self.refresh_processors(true);
 
-        let (total_time, compute_cpu, max_value) = if self.processors.is_empty() {
-            sysinfo_debug!("cannot compute processes CPU usage: no processor found...");
-            (0., false, 0.)
+            if self.processors.is_empty() {
+                sysinfo_debug!("cannot compute processes CPU usage: no processor found...");
+                (0., false, 0.)
+            } else {
+                let (new, old) = get_raw_times(&self.global_processor);
+                let total_time = if old > new { 1 } else { new - old };
+                (
+                    total_time as f32 / self.processors.len() as f32,
+                    true,
+                    self.get_max_process_cpu_usage(),
+                )
+            }
         } else {
-            let (new, old) = get_raw_times(&self.global_processor);
-            let total_time = if old > new { 1 } else { new - old };
-            (
-                total_time as f32 / self.processors.len() as f32,
-                true,
-                self.get_max_process_cpu_usage(),
-            )
+            (0., false, 0.)
         };
 
         let mut to_delete = Vec::with_capacity(20);
@@ -150,15 +192,49 @@ impl System {
         for (pid, proc_) in &mut self.process_list.tasks {
             if !has_been_updated(proc_) {
                 to_delete.push(*pid);
-            } else if compute_cpu {
+                continue;
+            }
+            if compute_cpu {
                 compute_cpu_usage(proc_, total_time, max_value);
             }
+            proc_.updated = false;
         }
         for pid in to_delete {
             self.process_list.tasks.remove(&pid);
         }
     }
 
+    // Computes the CPU usage of the given processes against the same CPU time snapshot.
+    fn update_processes_cpu_usage(&mut self, pids: &[Pid], refresh_kind: ProcessRefreshKind) {
+        let cpu_time = if refresh_kind.cpu() && !pids.is_empty() {
+            self.refresh_processors(true);
+
+            if self.processors.is_empty() {
+                sysinfo_debug!("Cannot compute process CPU usage: no processors found...");
+                None
+            } else {
+                let (new, old) = get_raw_times(&self.global_processor);
+                let total_time = (if old >= new { 1 } else { new - old }) as f32;
+                Some((
+                    total_time / self.processors.len() as f32,
+                    self.get_max_process_cpu_usage(),
+                ))
+            }
+        } else {
+            None
+        };
+
+        for pid in pids {
+            if let Some(p) = self.process_list.tasks.get_mut(pid) {
+                if let Some((total_time, max_value)) = cpu_time {
+                    compute_cpu_usage(p, total_time, max_value);
+                }
+                // The "updated" flag is only used when refreshing all processes.
+                p.updated = false;
+            }
+        }
+    }
+
     fn refresh_processors(&mut self, only_update_global_processor: bool) {
         if let Ok(f) = File::open("/proc/stat") {
             let buf = BufReader::new(f);
@@ -220,6 +296,9 @@ impl System {
                         vendor_id.clone(),
                         brand.clone(),
                     ));
+                    if let Some(processor) = self.processors.last_mut() {
+                        processor.numa_node = numa::cpu_node(&processor.name);
+                    }
                 } else {
                     parts.next(); // we don't want the name again
                     self.processors[i].set(
@@ -259,16 +338,12 @@ impl SystemExt for System {
     const IS_SUPPORTED: bool = true;
 
     fn new_with_specifics(refreshes: RefreshKind) -> System {
+        let boot_time = boot_time();
         let mut s = System {
             process_list: Process::new(0, None, 0),
-            mem_total: 0,
-            mem_free: 0,
-            mem_available: 0,
-            mem_buffers: 0,
-            mem_page_cache: 0,
-            mem_slab_reclaimable: 0,
-            swap_total: 0,
-            swap_free: 0,
+            memory_info: MemoryInfo::default(),
+            vm_stats: VmStats::default(),
+            vm_stats_refresh: None,
             global_processor: Processor::new_with_values(
                 "",
                 0,
@@ -286,12 +361,14 @@ impl SystemExt for System {
                 String::new(),
             ),
             processors: Vec::with_capacity(4),
-            page_size_kb: unsafe { sysconf(_SC_PAGESIZE) as u64 / 1024 },
+            page_size: unsafe { sysconf(_SC_PAGESIZE) as u64 },
             components: Vec::new(),
             disks: Vec::with_capacity(2),
             networks: Networks::new(),
+            cgroups: Cgroups::new(),
             users: Vec::new(),
-            boot_time: boot_time(),
+            boot_time,
+            precise_boot_time: precise_boot_time(|| boot_time),
         };
         if !refreshes.cpu() {
             s.refresh_processors(false); // We need the processors to be filled.
@@ -306,26 +383,15 @@ impl SystemExt for System {
 
     fn refresh_memory(&mut self) {
         if let Ok(data) = get_all_data("/proc/meminfo", 16_385) {
-            for line in data.split('\n') {
-                let mut iter = line.split(':');
-                let field = match iter.next() {
-                    Some("MemTotal") => &mut self.mem_total,
-                    Some("MemFree") => &mut self.mem_free,
-                    Some("MemAvailable") => &mut self.mem_available,
-                    Some("Buffers") => &mut self.mem_buffers,
-                    Some("Cached") => &mut self.mem_page_cache,
-                    Some("SReclaimable") => &mut self.mem_slab_reclaimable,
-                    Some("SwapTotal") => &mut self.swap_total,
-                    Some("SwapFree") => &mut self.swap_free,
-                    _ => continue,
-                };
-                if let Some(val_str) = iter.next().and_then(|s| s.trim_start().split(' ').next()) {
-                    if let Ok(value) = u64::from_str(val_str) {
-                        // /proc/meminfo reports KiB, though it says "kB". Convert it.
-                        *field = value * 128 / 125;
-                    }
-                }
-            }
+            self.memory_info = parse_meminfo(&data);
+        }
+        if let Ok(data) = get_all_data("/proc/vmstat", 16_385) {
+            let now = Instant::now();
+            let elapsed = self
+                .vm_stats_refresh
+                .map(|previous| now.duration_since(previous).as_secs_f64());
+            update_vm_stats(&mut self.vm_stats, &data, elapsed);
+            self.vm_stats_refresh = Some(now);
         }
     }
 
@@ -333,53 +399,77 @@ impl SystemExt for System {
         self.refresh_processors(false);
     }
 
-    fn refresh_processes(&mut self) {
-        let uptime = self.uptime();
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
         if refresh_procs(
             &mut self.process_list,
             Path::new("/proc"),
-            self.page_size_kb,
+            self.page_size,
             0,
-            uptime,
-            get_secs_since_epoch(),
+            self.precise_boot_time,
+            refresh_kind,
         ) {
-            self.clear_procs();
+            self.clear_procs(refresh_kind);
         }
     }
 
-    fn refresh_process(&mut self, pid: Pid) -> bool {
-        let uptime = self.uptime();
+    fn refresh_process_specifics(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
         let found = match _get_process_data(
             &Path::new("/proc/").join(pid.to_string()),
             &mut self.process_list,
-            self.page_size_kb,
+            self.page_size,
             0,
-            uptime,
-            get_secs_since_epoch(),
+            self.precise_boot_time,
+            refresh_kind,
         ) {
             Ok((Some(p), pid)) => {
                 self.process_list.tasks.insert(pid, p);
                 true
             }
             Ok(_) => true,
-            Err(_) => false,
+            Err(_) => {
+                // Kernel threads aren't kept if they're not listed anymore.
+                if !refresh_kind.kernel_threads()
+                    && self
+                        .process_list
+                        .tasks
+                        .get(&pid)
+                        .map_or(false, |p| p.is_kernel_thread())
+                {
+                    self.process_list.tasks.remove(&pid);
+                }
+                false
+            }
         };
         if found {
-            self.refresh_processors(true);
+            self.update_processes_cpu_usage(&[pid], refresh_kind);
+        }
+        found
+    }
 
-            if self.processors.is_empty() {
-                sysinfo_debug!("Cannot compute process CPU usage: no processors found...");
-                return found;
-            }
-            let (new, old) = get_raw_times(&self.global_processor);
-            let total_time = (if old >= new { 1 } else { new - old }) as f32;
+    fn refresh_processes_list_specifics(&mut self, pids: &[Pid], refresh_kind: ProcessRefreshKind) {
+        let mut found = Vec::with_capacity(pids.len());
 
-            let max_cpu_usage = self.get_max_process_cpu_usage();
-            if let Some(p) = self.process_list.tasks.get_mut(&pid) {
-                compute_cpu_usage(p, total_time / self.processors.len() as f32, max_cpu_usage);
+        for &pid in pids {
+            match _get_process_data(
+                &Path::new("/proc/").join(pid.to_string()),
+                &mut self.process_list,
+                self.page_size,
+                0,
+                self.precise_boot_time,
+                refresh_kind,
+            ) {
+                Ok((Some(p), pid)) => {
+                    self.process_list.tasks.insert(pid, p);
+                    found.push(pid);
+                }
+                Ok((None, pid)) => found.push(pid),
+                Err(_) => {
+                    // The process doesn't exist anymore.
+                    self.process_list.tasks.remove(&pid);
+                }
             }
         }
-        found
+        self.update_processes_cpu_usage(&found, refresh_kind);
     }
 
     fn refresh_disks_list(&mut self) {
@@ -410,6 +500,14 @@ impl SystemExt for System {
         &mut self.networks
     }
 
+    fn cgroups(&self) -> &Cgroups {
+        &self.cgroups
+    }
+
+    fn cgroups_mut(&mut self) -> &mut Cgroups {
+        &mut self.cgroups
+    }
+
     fn global_processor_info(&self) -> &Processor {
         &self.global_processor
     }
@@ -423,36 +521,85 @@ impl SystemExt for System {
     }
 
     fn total_memory(&self) -> u64 {
-        self.mem_total
+        self.memory_info.total
     }
 
     fn free_memory(&self) -> u64 {
-        self.mem_free
+        self.memory_info.free
     }
 
     fn available_memory(&self) -> u64 {
-        self.mem_available
+        self.memory_info.available
     }
 
     fn used_memory(&self) -> u64 {
-        self.mem_total
-            - self.mem_free
-            - self.mem_buffers
-            - self.mem_page_cache
-            - self.mem_slab_reclaimable
+        let m = &self.memory_info;
+        m.total - m.free - m.buffers - m.cached - m.s_reclaimable
     }
 
     fn total_swap(&self) -> u64 {
-        self.swap_total
+        self.memory_info.swap_total
     }
 
     fn free_swap(&self) -> u64 {
-        self.swap_free
+        self.memory_info.swap_free
     }
 
     // need to be checked
     fn used_swap(&self) -> u64 {
-        self.swap_total - self.swap_free
+        self.memory_info.swap_total - self.memory_info.swap_free
+    }
+
+    fn memory_info(&self) -> &MemoryInfo {
+        &self.memory_info
+    }
+
+    fn effective_memory_limit(&self) -> u64 {
+        let total = self.total_memory();
+
+        match cgroup::current_cgroup("memory").and_then(|c| cgroup::memory_limit_and_usage(&c)) {
+            Some((limit, _)) if limit < total => limit,
+            _ => total,
+        }
+    }
+
+    fn effective_memory_usage(&self) -> u64 {
+        match cgroup::current_cgroup("memory").and_then(|c| cgroup::memory_limit_and_usage(&c)) {
+            Some((limit, Some(usage))) if limit < self.total_memory() => usage,
+            _ => self.used_memory(),
+        }
+    }
+
+    fn effective_cpu_count(&self) -> usize {
+        let mut count = cgroup::current_cgroup("cpuset")
+            .and_then(|cgroup| cgroup::cpuset(&cgroup))
+            .map(|cpus| cpus.len())
+            .unwrap_or_else(|| self.processors.len());
+
+        if let Some(limit) = cgroup::current_cgroup("cpu").and_then(|c| cgroup::cpu_limit(&c)) {
+            count = count.min((limit / 100.).ceil() as usize);
+        }
+        count.max(1)
+    }
+
+    fn vm_stats(&self) -> &VmStats {
+        &self.vm_stats
+    }
+
+    fn swaps(&self) -> Vec<Swap> {
+        swap::get_swaps()
+    }
+
+    fn numa_nodes(&self) -> Vec<NumaNode> {
+        numa::get_numa_nodes()
+    }
+
+    fn virtualization(&self) -> Virtualization {
+        virtualization::get_virtualization()
+    }
+
+    fn hardware_info(&self) -> HardwareInfo {
+        dmi::get_hardware_info()
     }
 
     fn components(&self) -> &[Component] {
@@ -505,15 +652,69 @@ impl SystemExt for System {
         }
     }
 
+    fn pressure(&self) -> Pressure {
+        let read = |resource| read_pressure(Path::new("/proc/pressure").join(resource));
+
+        Pressure {
+            cpu: read("cpu"),
+            memory: read("memory"),
+            io: read("io"),
+            irq: read("irq"),
+        }
+    }
+
+    fn cgroup_pressure(&self, cgroup: &Path) -> Pressure {
+        let read = |resource| cgroup::pressure_file(cgroup, resource).and_then(read_pressure);
+
+        Pressure {
+            cpu: read("cpu"),
+            memory: read("memory"),
+            io: read("io"),
+            irq: read("irq"),
+        }
+    }
+
     fn users(&self) -> &[User] {
         &self.users
     }
 
+    fn sessions(&self) -> Vec<Session> {
+        utmp::get_sessions()
+    }
+
+    fn login_history(&self) -> Vec<LoginRecord> {
+        utmp::get_login_history()
+    }
+
+    fn systemd_units(&self) -> Vec<SystemdUnit> {
+        if !cgroup::is_systemd_booted() {
+            return Vec::new();
+        }
+        let mut units = group_processes(&self.process_list.tasks, |p| {
+            cgroup::systemd_unit(p.cgroup())
+        })
+        .into_iter()
+        .map(|((name, cgroup), usage)| {
+            let limits = cgroup::read_limits(&cgroup);
+            SystemdUnit {
+                name,
+                cgroup,
+                usage,
+                memory_limit: limits.memory,
+                cpu_limit: limits.cpu,
+                tasks_limit: limits.tasks,
+            }
+        })
+        .collect::<Vec<_>>();
+        units.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        units
+    }
+
     #[cfg(not(target_os = "android"))]
     fn name(&self) -> Option<String> {
         get_system_info_linux(
             InfoType::Name,
-            Path::new("/etc/os-release"),
+            os_release::get_os_release(),
             Path::new("/etc/lsb-release"),
         )
     }
@@ -572,11 +773,15 @@ impl SystemExt for System {
         }
     }
 
+    fn parsed_kernel_version(&self) -> Option<KernelVersion> {
+        crate::utils::uname_kernel_version()
+    }
+
     #[cfg(not(target_os = "android"))]
     fn os_version(&self) -> Option<String> {
         get_system_info_linux(
             InfoType::OsVersion,
-            Path::new("/etc/os-release"),
+            os_release::get_os_release(),
             Path::new("/etc/lsb-release"),
         )
     }
@@ -585,6 +790,16 @@ impl SystemExt for System {
     fn os_version(&self) -> Option<String> {
         get_system_info_android(InfoType::OsVersion)
     }
+
+    #[cfg(not(target_os = "android"))]
+    fn os_release(&self) -> Option<OsRelease> {
+        os_release::get_os_release()
+    }
+
+    #[cfg(target_os = "android")]
+    fn os_release(&self) -> Option<OsRelease> {
+        None
+    }
 }
 
 impl Default for System {
@@ -613,18 +828,19 @@ enum InfoType {
 }
 
 #[cfg(not(target_os = "android"))]
-fn get_system_info_linux(info: InfoType, path: &Path, fallback_path: &Path) -> Option<String> {
-    if let Ok(f) = File::open(path) {
-        let reader = BufReader::new(f);
-
-        let info_str = match info {
-            InfoType::Name => "NAME=",
-            InfoType::OsVersion => "VERSION_ID=",
-        };
-
-        for line in reader.lines().flatten() {
-            if let Some(stripped) = line.strip_prefix(info_str) {
-                return Some(stripped.replace("\"", ""));
+fn get_system_info_linux(
+    info: InfoType,
+    os_release: Option<OsRelease>,
+    fallback_path: &Path,
+) -> Option<String> {
+    if let Some(os_release) = os_release {
+        match info {
+            // `NAME` defaults to "Linux" so it is always set, like in `SystemExt::os_release`.
+            InfoType::Name => return Some(os_release.name),
+            InfoType::OsVersion => {
+                if os_release.version_id.is_some() {
+                    return os_release.version_id;
+                }
             }
         }
     }
@@ -675,11 +891,156 @@ fn get_system_info_android(info: InfoType) -> Option<String> {
     }
 }
 
-fn get_secs_since_epoch() -> u64 {
-    match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-        Ok(n) => n.as_secs(),
-        _ => panic!("SystemTime before UNIX EPOCH!"),
+fn read_pressure<P: AsRef<Path>>(path: P) -> Option<ResourcePressure> {
+    get_all_data(path, 256)
+        .ok()
+        .map(|data| parse_pressure(&data))
+}
+
+/// Parses a Pressure Stall Information file, which looks like:
+///
+/// ```text
+/// some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+/// full avg10=0.00 avg60=0.00 avg300=0.00 total=0
+/// ```
+fn parse_pressure(data: &str) -> ResourcePressure {
+    let mut pressure = ResourcePressure::default();
+
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let field = match parts.next() {
+            Some("some") => &mut pressure.some,
+            Some("full") => &mut pressure.full,
+            _ => continue,
+        };
+        let mut stats = PressureStats::default();
+        for (key, value) in parts.filter_map(|part| {
+            let mut iter = part.splitn(2, '=');
+            Some((iter.next()?, iter.next()?))
+        }) {
+            match key {
+                "avg10" => stats.avg10 = f64::from_str(value).unwrap_or(0.),
+                "avg60" => stats.avg60 = f64::from_str(value).unwrap_or(0.),
+                "avg300" => stats.avg300 = f64::from_str(value).unwrap_or(0.),
+                "total" => stats.total = u64::from_str(value).unwrap_or(0),
+                _ => {}
+            }
+        }
+        *field = Some(stats);
     }
+    pressure
+}
+
+/// Counters of `/proc/vmstat` which are summed into the `pgscan` and `pgsteal` fields.
+const PGSCAN_COUNTERS: &[&str] = &["pgscan_kswapd", "pgscan_direct", "pgscan_khugepaged"];
+const PGSTEAL_COUNTERS: &[&str] = &["pgsteal_kswapd", "pgsteal_direct", "pgsteal_khugepaged"];
+
+fn update_vm_counter(counter: &mut VmCounter, total: u64, elapsed: Option<f64>) {
+    counter.per_second = match elapsed {
+        Some(elapsed) if elapsed > 0. => total.saturating_sub(counter.total) as f64 / elapsed,
+        _ => 0.,
+    };
+    counter.total = total;
+}
+
+/// Updates `stats` with the content of `/proc/vmstat`. `elapsed` is the number of seconds since
+/// the previous update.
+fn update_vm_stats(stats: &mut VmStats, data: &str, elapsed: Option<f64>) {
+    let mut pgscan = 0;
+    let mut pgsteal = 0;
+
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let (key, value) = match (parts.next(), parts.next().map(u64::from_str)) {
+            (Some(key), Some(Ok(value))) => (key, value),
+            _ => continue,
+        };
+        if PGSCAN_COUNTERS.contains(&key) {
+            pgscan += value;
+        } else if PGSTEAL_COUNTERS.contains(&key) {
+            pgsteal += value;
+        }
+        let counter = match key {
+            "pgpgin" => &mut stats.pgpgin,
+            "pgpgout" => &mut stats.pgpgout,
+            "pswpin" => &mut stats.pswpin,
+            "pswpout" => &mut stats.pswpout,
+            "pgfault" => &mut stats.pgfault,
+            "pgmajfault" => &mut stats.pgmajfault,
+            "oom_kill" => &mut stats.oom_kill,
+            "thp_fault_alloc" => &mut stats.thp_fault_alloc,
+            "thp_fault_fallback" => &mut stats.thp_fault_fallback,
+            "thp_collapse_alloc" => &mut stats.thp_collapse_alloc,
+            "thp_split_page" => &mut stats.thp_split_page,
+            _ => stats.others.entry(key.to_owned()).or_default(),
+        };
+        update_vm_counter(counter, value, elapsed);
+    }
+    update_vm_counter(&mut stats.pgscan, pgscan, elapsed);
+    update_vm_counter(&mut stats.pgsteal, pgsteal, elapsed);
+}
+
+fn parse_meminfo(data: &str) -> MemoryInfo {
+    let mut info = MemoryInfo::default();
+
+    for line in data.lines() {
+        let mut iter = line.split(':');
+        let (key, mut values) = match (iter.next(), iter.next()) {
+            (Some(key), Some(values)) => (key, values.split_whitespace()),
+            _ => continue,
+        };
+        let value = match values.next().map(u64::from_str) {
+            Some(Ok(value)) => value,
+            _ => continue,
+        };
+        // /proc/meminfo reports KiB, though it says "kB". The huge pages counts have no unit.
+        let value = match values.next() {
+            Some("kB") => value * 1_024,
+            _ => value,
+        };
+        let field = match key {
+            "MemTotal" => &mut info.total,
+            "MemFree" => &mut info.free,
+            "MemAvailable" => &mut info.available,
+            "Buffers" => &mut info.buffers,
+            "Cached" => &mut info.cached,
+            "SwapCached" => &mut info.swap_cached,
+            "Active" => &mut info.active,
+            "Inactive" => &mut info.inactive,
+            "Active(anon)" => &mut info.active_anon,
+            "Inactive(anon)" => &mut info.inactive_anon,
+            "Active(file)" => &mut info.active_file,
+            "Inactive(file)" => &mut info.inactive_file,
+            "Unevictable" => &mut info.unevictable,
+            "Mlocked" => &mut info.mlocked,
+            "SwapTotal" => &mut info.swap_total,
+            "SwapFree" => &mut info.swap_free,
+            "Dirty" => &mut info.dirty,
+            "Writeback" => &mut info.writeback,
+            "AnonPages" => &mut info.anon_pages,
+            "Mapped" => &mut info.mapped,
+            "Shmem" => &mut info.shmem,
+            "SReclaimable" => &mut info.s_reclaimable,
+            "SUnreclaim" => &mut info.s_unreclaim,
+            "KernelStack" => &mut info.kernel_stack,
+            "PageTables" => &mut info.page_tables,
+            "CommitLimit" => &mut info.commit_limit,
+            "Committed_AS" => &mut info.committed_as,
+            "VmallocUsed" => &mut info.vmalloc_used,
+            "AnonHugePages" => &mut info.anon_huge_pages,
+            "HugePages_Total" => &mut info.huge_pages_total,
+            "HugePages_Free" => &mut info.huge_pages_free,
+            "HugePages_Rsvd" => &mut info.huge_pages_reserved,
+            "HugePages_Surp" => &mut info.huge_pages_surplus,
+            "Hugepagesize" => &mut info.huge_page_size,
+            _ => {
+                info.others.insert(key.to_owned(), value);
+                continue;
+            }
+        };
+        *field = value;
+    }
+    info
 }
 
 #[cfg(test)]
@@ -688,7 +1049,8 @@ mod test {
     use super::get_system_info_android;
     #[cfg(not(target_os = "android"))]
     use super::get_system_info_linux;
-    use super::InfoType;
+    use super::{parse_meminfo, parse_pressure, update_vm_stats, InfoType};
+    use crate::{PressureStats, VmStats};
 
     #[test]
     #[cfg(target_os = "android")]
@@ -700,8 +1062,11 @@ mod test {
     #[test]
     #[cfg(not(target_os = "android"))]
     fn lsb_release_fallback_not_android() {
+        use crate::sys::os_release::read_os_release;
         use std::path::Path;
 
+        let os_release = |path: &Path| read_os_release(&[Path::new("/non-existent"), path]);
+
         let dir = tempfile::tempdir().expect("failed to create temporary directory");
         let tmp1 = dir.path().join("tmp1");
         let tmp2 = dir.path().join("tmp2");
@@ -734,22 +1099,133 @@ DISTRIB_DESCRIPTION="Ubuntu 20.10"
 
         // Check for the "normal" path: "/etc/os-release"
         assert_eq!(
-            get_system_info_linux(InfoType::OsVersion, &tmp1, Path::new("")),
+            get_system_info_linux(InfoType::OsVersion, os_release(&tmp1), Path::new("")),
             Some("20.10".to_owned())
         );
         assert_eq!(
-            get_system_info_linux(InfoType::Name, &tmp1, Path::new("")),
+            get_system_info_linux(InfoType::Name, os_release(&tmp1), Path::new("")),
             Some("Ubuntu".to_owned())
         );
 
         // Check for the "fallback" path: "/etc/lsb-release"
         assert_eq!(
-            get_system_info_linux(InfoType::OsVersion, Path::new(""), &tmp2),
+            get_system_info_linux(InfoType::OsVersion, None, &tmp2),
             Some("20.10".to_owned())
         );
         assert_eq!(
-            get_system_info_linux(InfoType::Name, Path::new(""), &tmp2),
+            get_system_info_linux(InfoType::Name, None, &tmp2),
             Some("Ubuntu".to_owned())
         );
+
+        // `VERSION_ID` is optional so it falls back to "/etc/lsb-release" but `NAME` defaults to
+        // "Linux".
+        std::fs::write(&tmp1, "ID=ubuntu\n").expect("Failed to write tmp1");
+        assert_eq!(
+            get_system_info_linux(InfoType::OsVersion, os_release(&tmp1), &tmp2),
+            Some("20.10".to_owned())
+        );
+        assert_eq!(
+            get_system_info_linux(InfoType::Name, os_release(&tmp1), &tmp2),
+            Some("Linux".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let info = parse_meminfo(
+            "MemTotal:       16000000 kB
+MemFree:         1000000 kB
+Active(anon):     250000 kB
+Committed_AS:   20000000 kB
+CommitLimit:    10000000 kB
+HugePages_Total:      16
+Hugepagesize:       2048 kB
+Zswap:               125 kB
+Percpu:              500 kB
+",
+        );
+        assert_eq!(info.total, 16_384_000_000);
+        assert_eq!(info.free, 1_024_000_000);
+        assert_eq!(info.active_anon, 256_000_000);
+        assert_eq!(info.committed_as, 20_480_000_000);
+        assert_eq!(info.commit_limit, 10_240_000_000);
+        assert_eq!(info.huge_pages_total, 16);
+        assert_eq!(info.huge_page_size, 2_097_152);
+        assert_eq!(info.others.len(), 2);
+        assert_eq!(info.others.get("Zswap"), Some(&128_000));
+        assert_eq!(info.others.get("Percpu"), Some(&512_000));
+    }
+
+    #[test]
+    fn test_parse_pressure() {
+        let pressure = parse_pressure(
+            "some avg10=1.50 avg60=0.75 avg300=0.10 total=123456
+full avg10=0.00 avg60=0.00 avg300=0.00 total=42
+",
+        );
+        assert_eq!(
+            pressure.some,
+            Some(PressureStats {
+                avg10: 1.5,
+                avg60: 0.75,
+                avg300: 0.1,
+                total: 123_456,
+            })
+        );
+        assert_eq!(pressure.full.map(|full| full.total), Some(42));
+
+        let pressure = parse_pressure("full avg10=2.00 avg60=0.00 avg300=0.00 total=7\n");
+        assert_eq!(pressure.some, None);
+        assert_eq!(pressure.full.map(|full| full.avg10), Some(2.));
+    }
+
+    #[test]
+    fn test_update_vm_stats() {
+        let mut stats = VmStats::default();
+        update_vm_stats(
+            &mut stats,
+            "pgpgin 100
+pswpout 10
+pgscan_kswapd 5
+pgscan_direct 2
+pgscan_anon 7
+pgsteal_kswapd 3
+oom_kill 0
+nr_free_pages 1000
+",
+            None,
+        );
+        assert_eq!(stats.pgpgin.total, 100);
+        assert_eq!(stats.pgpgin.per_second, 0.);
+        assert_eq!(stats.pgscan.total, 7);
+        assert_eq!(stats.pgsteal.total, 3);
+        assert_eq!(
+            stats.others.get("nr_free_pages").map(|c| c.total),
+            Some(1_000)
+        );
+
+        update_vm_stats(
+            &mut stats,
+            "pgpgin 300
+pswpout 10
+pgscan_kswapd 9
+pgscan_direct 2
+pgscan_anon 11
+pgsteal_kswapd 3
+oom_kill 1
+nr_free_pages 500
+",
+            Some(2.),
+        );
+        assert_eq!(stats.pgpgin.total, 300);
+        assert_eq!(stats.pgpgin.per_second, 100.);
+        assert_eq!(stats.pswpout.per_second, 0.);
+        assert_eq!(stats.pgscan.per_second, 2.);
+        assert_eq!(stats.oom_kill.per_second, 0.5);
+        // Gauges can decrease.
+        assert_eq!(
+            stats.others.get("nr_free_pages").map(|c| c.per_second),
+            Some(0.)
+        );
     }
 }
@@ -0,0 +1,261 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::{LoginRecord, Pid, Session};
+
+use std::collections::HashMap;
+use std::os::raw::c_char;
+use std::path::Path;
+
+#[cfg(target_os = "android")]
+use libc::utmp;
+#[cfg(not(target_os = "android"))]
+use libc::utmpx as utmp;
+
+/// The layout of the records depends on the architecture: some of them store the time on 32 bits
+/// even on 64-bit targets, so the record is read through the `libc` definition.
+const RECORD_SIZE: usize = std::mem::size_of::<utmp>();
+
+const RUN_LVL: i16 = 1;
+const BOOT_TIME: i16 = 2;
+const USER_PROCESS: i16 = 7;
+const DEAD_PROCESS: i16 = 8;
+
+struct Record {
+    type_: i16,
+    pid: Pid,
+    line: String,
+    user: String,
+    host: String,
+    time: u64,
+}
+
+impl Record {
+    fn into_session(self) -> Session {
+        Session {
+            user: self.user,
+            tty: self.line,
+            host: if self.host.is_empty() {
+                None
+            } else {
+                Some(self.host)
+            },
+            login_time: self.time,
+            pid: self.pid,
+        }
+    }
+}
+
+fn read_string(data: &[c_char]) -> String {
+    let data = data
+        .iter()
+        .take_while(|c| **c != 0)
+        .map(|c| *c as u8)
+        .collect::<Vec<_>>();
+    String::from_utf8_lossy(&data).into_owned()
+}
+
+// The type of `tv_sec` depends on the architecture.
+#[allow(clippy::useless_conversion)]
+fn parse_records(data: &[u8]) -> Vec<Record> {
+    data.chunks_exact(RECORD_SIZE)
+        .map(|record| {
+            // The data isn't aligned for `utmp`.
+            let record = unsafe { std::ptr::read_unaligned(record.as_ptr() as *const utmp) };
+            Record {
+                type_: record.ut_type,
+                pid: record.ut_pid as Pid,
+                line: read_string(&record.ut_line),
+                user: read_string(&record.ut_user),
+                host: read_string(&record.ut_host),
+                time: i64::from(record.ut_tv.tv_sec).max(0) as u64,
+            }
+        })
+        .collect()
+}
+
+fn read_records(path: &Path) -> Vec<Record> {
+    std::fs::read(path)
+        .map(|data| parse_records(&data))
+        .unwrap_or_default()
+}
+
+pub(crate) fn get_sessions() -> Vec<Session> {
+    sessions(read_records(Path::new("/var/run/utmp")))
+}
+
+/// Entries of processes which exited without cleaning `utmp` are skipped.
+fn sessions(records: Vec<Record>) -> Vec<Session> {
+    records
+        .into_iter()
+        .filter(|r| r.type_ == USER_PROCESS && !r.user.is_empty())
+        .filter(|r| Path::new("/proc").join(r.pid.to_string()).exists())
+        .map(Record::into_session)
+        .collect()
+}
+
+pub(crate) fn get_login_history() -> Vec<LoginRecord> {
+    login_history(read_records(Path::new("/var/log/wtmp")))
+}
+
+/// Matches the logins with the logouts on the same terminal. Like `last`, a reboot or a shutdown
+/// ends all the sessions which are still open.
+fn login_history(records: Vec<Record>) -> Vec<LoginRecord> {
+    let mut history: Vec<LoginRecord> = Vec::new();
+    // Index in `history` of the open session on each terminal.
+    let mut open: HashMap<String, usize> = HashMap::new();
+
+    for record in records {
+        match record.type_ {
+            USER_PROCESS if !record.user.is_empty() => {
+                let line = record.line.clone();
+                // A new login on a terminal means the previous session on it is over.
+                if let Some(index) = open.insert(line, history.len()) {
+                    history[index].logout_time = Some(record.time);
+                }
+                history.push(LoginRecord {
+                    session: record.into_session(),
+                    logout_time: None,
+                });
+            }
+            // Logouts are `USER_PROCESS` records with an empty user on some systems.
+            USER_PROCESS | DEAD_PROCESS => {
+                if let Some(index) = open.remove(&record.line) {
+                    history[index].logout_time = Some(record.time);
+                }
+            }
+            RUN_LVL | BOOT_TIME if record.type_ == BOOT_TIME || record.user == "shutdown" => {
+                for (_, index) in open.drain() {
+                    history[index].logout_time = Some(record.time);
+                }
+            }
+            _ => {}
+        }
+    }
+    history
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(type_: i16, pid: i32, line: &str, user: &str, host: &str, time: i32) -> Vec<u8> {
+        fn write_string(dest: &mut [c_char], s: &str) {
+            for (d, c) in dest.iter_mut().zip(s.bytes()) {
+                *d = c as c_char;
+            }
+        }
+
+        let mut record: utmp = unsafe { std::mem::zeroed() };
+        record.ut_type = type_;
+        record.ut_pid = pid;
+        write_string(&mut record.ut_line, line);
+        write_string(&mut record.ut_user, user);
+        write_string(&mut record.ut_host, host);
+        record.ut_tv.tv_sec = time as _;
+        unsafe { std::slice::from_raw_parts(&record as *const utmp as *const u8, RECORD_SIZE) }
+            .to_vec()
+    }
+
+    #[test]
+    #[cfg(all(
+        target_env = "gnu",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    fn test_record_size() {
+        // The time is a full `timeval` on aarch64.
+        let size = if cfg!(target_arch = "aarch64") {
+            400
+        } else {
+            384
+        };
+        assert_eq!(RECORD_SIZE, size);
+    }
+
+    fn parse(records: &[Vec<u8>]) -> Vec<Record> {
+        parse_records(&records.concat())
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let records = parse(&[
+            record(BOOT_TIME, 0, "~", "reboot", "6.1.0", 1_000),
+            record(USER_PROCESS, 1234, "pts/0", "alice", "10.0.0.1", 1_100),
+        ]);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].type_, BOOT_TIME);
+        assert_eq!(records[1].pid, 1234);
+        assert_eq!(records[1].line, "pts/0");
+        assert_eq!(records[1].user, "alice");
+        assert_eq!(records[1].host, "10.0.0.1");
+        assert_eq!(records[1].time, 1_100);
+
+        // An incomplete record is ignored.
+        let mut data = record(USER_PROCESS, 1, "tty1", "bob", "", 1);
+        data.pop();
+        assert!(parse_records(&data).is_empty());
+    }
+
+    #[test]
+    fn test_sessions() {
+        let pid = std::process::id() as i32;
+        let sessions = sessions(parse(&[
+            record(USER_PROCESS, pid, "pts/0", "alice", "10.0.0.1", 1_100),
+            record(USER_PROCESS, pid, "tty1", "bob", "", 1_200),
+            record(DEAD_PROCESS, pid, "pts/1", "", "", 1_300),
+            // This process doesn't exist.
+            record(USER_PROCESS, i32::MAX, "pts/2", "carol", "", 1_400),
+        ]));
+
+        assert_eq!(
+            sessions,
+            vec![
+                Session {
+                    user: "alice".to_owned(),
+                    tty: "pts/0".to_owned(),
+                    host: Some("10.0.0.1".to_owned()),
+                    login_time: 1_100,
+                    pid: pid as Pid,
+                },
+                Session {
+                    user: "bob".to_owned(),
+                    tty: "tty1".to_owned(),
+                    host: None,
+                    login_time: 1_200,
+                    pid: pid as Pid,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_login_history() {
+        let history = login_history(parse(&[
+            record(BOOT_TIME, 0, "~", "reboot", "", 1_000),
+            record(USER_PROCESS, 10, "pts/0", "alice", "10.0.0.1", 1_100),
+            record(USER_PROCESS, 11, "tty1", "bob", "", 1_200),
+            record(DEAD_PROCESS, 10, "pts/0", "", "", 1_300),
+            record(USER_PROCESS, 12, "pts/0", "carol", "10.0.0.2", 1_400),
+            // The system crashed, then rebooted.
+            record(BOOT_TIME, 0, "~", "reboot", "", 2_000),
+            record(USER_PROCESS, 13, "pts/0", "alice", "10.0.0.1", 2_100),
+            record(RUN_LVL, 0, "~", "runlevel", "", 2_150),
+        ]));
+
+        let summary = history
+            .iter()
+            .map(|r| (r.session.user.as_str(), r.session.login_time, r.logout_time))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("alice", 1_100, Some(1_300)),
+                ("bob", 1_200, Some(2_000)),
+                ("carol", 1_400, Some(2_000)),
+                ("alice", 2_100, None),
+            ]
+        );
+        assert_eq!(history[0].session.pid, 10);
+        assert_eq!(history[0].session.host.as_deref(), Some("10.0.0.1"));
+    }
+}
@@ -0,0 +1,193 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::sys::dmi;
+use crate::sys::utils::get_all_data;
+use crate::Virtualization;
+
+use std::path::Path;
+
+/// Detects the virtualization the same way `systemd-detect-virt` does: containers are checked
+/// first since they can run inside a virtual machine.
+pub(crate) fn get_virtualization() -> Virtualization {
+    match container() {
+        Virtualization::None => vm(),
+        container => container,
+    }
+}
+
+fn read(path: &str) -> Option<String> {
+    get_all_data(path, 4_096).ok()
+}
+
+fn container() -> Virtualization {
+    let cgroup = read("/proc/1/cgroup").unwrap_or_default();
+    // Pods can use any container runtime so it has to be checked first.
+    if cgroup.contains("kubepods")
+        || std::env::var_os("KUBERNETES_SERVICE_HOST").is_some()
+        || Path::new("/var/run/secrets/kubernetes.io").exists()
+    {
+        return Virtualization::Kubernetes;
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Virtualization::Podman;
+    }
+    if Path::new("/.dockerenv").exists() {
+        return Virtualization::Docker;
+    }
+    // Set by systemd when it runs in a container.
+    if let Some(name) = read("/run/systemd/container") {
+        return container_from_name(name.trim());
+    }
+    container_from_cgroup(&cgroup)
+}
+
+/// Converts the value of the `container` environment variable (as written in
+/// `/run/systemd/container`).
+fn container_from_name(name: &str) -> Virtualization {
+    match name {
+        "" => Virtualization::None,
+        "docker" => Virtualization::Docker,
+        "podman" => Virtualization::Podman,
+        "lxc" | "lxc-libvirt" => Virtualization::Lxc,
+        "systemd-nspawn" => Virtualization::SystemdNspawn,
+        _ => Virtualization::OtherContainer,
+    }
+}
+
+/// Guesses the container runtime from the control groups of the init process (`/proc/1/cgroup`).
+/// It doesn't work with cgroup namespaces, where the paths start at the root of the container.
+fn container_from_cgroup(cgroup: &str) -> Virtualization {
+    for line in cgroup.lines() {
+        let path = match line.splitn(3, ':').nth(2) {
+            Some(path) => path,
+            None => continue,
+        };
+        if path.contains("kubepods") {
+            return Virtualization::Kubernetes;
+        } else if path.contains("libpod") {
+            return Virtualization::Podman;
+        } else if path.starts_with("/docker/") || path.contains("/docker-") {
+            return Virtualization::Docker;
+        } else if path.starts_with("/lxc/") || path.starts_with("/lxc.payload") {
+            return Virtualization::Lxc;
+        } else if path.starts_with("/machine.slice/machine-") {
+            return Virtualization::SystemdNspawn;
+        }
+    }
+    Virtualization::None
+}
+
+fn vm() -> Virtualization {
+    let osrelease = read("/proc/sys/kernel/osrelease").unwrap_or_default();
+    if osrelease.contains("microsoft") || osrelease.contains("Microsoft") {
+        return Virtualization::Wsl;
+    }
+    for file in &["sys_vendor", "product_name", "board_vendor", "bios_vendor"] {
+        if let Some(value) = dmi::read(file) {
+            let vm = vm_from_dmi(&value);
+            if vm != Virtualization::None {
+                return vm;
+            }
+        }
+    }
+    if read("/sys/hypervisor/type").map_or(false, |t| t.trim() == "xen") {
+        return Virtualization::Xen;
+    }
+    // The hypervisor is unknown but the CPU tells us we're running in a virtual machine.
+    if read_cpuinfo_flags().map_or(false, |flags| {
+        flags.split_whitespace().any(|f| f == "hypervisor")
+    }) {
+        return Virtualization::OtherVm;
+    }
+    Virtualization::None
+}
+
+fn read_cpuinfo_flags() -> Option<String> {
+    let cpuinfo = get_all_data("/proc/cpuinfo", 16_385).ok()?;
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("flags"))
+        .map(|line| line.to_owned())
+}
+
+/// Converts a value of `/sys/class/dmi/id`, which is the name of the hypervisor's vendor.
+fn vm_from_dmi(value: &str) -> Virtualization {
+    if value.starts_with("KVM") || value.starts_with("Amazon EC2") || value == "Google" {
+        Virtualization::Kvm
+    } else if value.starts_with("QEMU") {
+        Virtualization::Qemu
+    } else if value.starts_with("VMware") || value.starts_with("VMW") {
+        Virtualization::VMware
+    } else if value.starts_with("Xen") {
+        Virtualization::Xen
+    } else if value == "Virtual Machine" || value.starts_with("Microsoft Corporation Virtual") {
+        // "Microsoft Corporation" alone is also used for Surface computers.
+        Virtualization::HyperV
+    } else if value.starts_with("innotek GmbH") || value.starts_with("VirtualBox") {
+        Virtualization::VirtualBox
+    } else {
+        Virtualization::None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{container_from_cgroup, container_from_name, vm_from_dmi};
+    use crate::Virtualization;
+
+    #[test]
+    fn test_container_from_cgroup() {
+        assert_eq!(
+            container_from_cgroup("12:memory:/docker/0123abcd\n0::/docker/0123abcd\n"),
+            Virtualization::Docker
+        );
+        assert_eq!(
+            container_from_cgroup("0::/system.slice/docker-0123abcd.scope\n"),
+            Virtualization::Docker
+        );
+        assert_eq!(
+            container_from_cgroup("0::/kubepods/besteffort/pod1234/0123abcd\n"),
+            Virtualization::Kubernetes
+        );
+        assert_eq!(
+            container_from_cgroup("0::/machine.slice/libpod-0123abcd.scope\n"),
+            Virtualization::Podman
+        );
+        assert_eq!(
+            container_from_cgroup("0::/lxc.payload.test\n"),
+            Virtualization::Lxc
+        );
+        assert_eq!(
+            container_from_cgroup("0::/machine.slice/machine-test.scope\n"),
+            Virtualization::SystemdNspawn
+        );
+        assert_eq!(
+            container_from_cgroup("0::/init.scope\n"),
+            Virtualization::None
+        );
+        assert_eq!(container_from_cgroup("0::/\n"), Virtualization::None);
+    }
+
+    #[test]
+    fn test_container_from_name() {
+        assert_eq!(container_from_name("podman"), Virtualization::Podman);
+        assert_eq!(
+            container_from_name("systemd-nspawn"),
+            Virtualization::SystemdNspawn
+        );
+        assert_eq!(container_from_name("wsl"), Virtualization::OtherContainer);
+        assert_eq!(container_from_name(""), Virtualization::None);
+    }
+
+    #[test]
+    fn test_vm_from_dmi() {
+        assert_eq!(vm_from_dmi("QEMU"), Virtualization::Qemu);
+        assert_eq!(vm_from_dmi("KVM"), Virtualization::Kvm);
+        assert_eq!(vm_from_dmi("VMware, Inc."), Virtualization::VMware);
+        assert_eq!(vm_from_dmi("Xen"), Virtualization::Xen);
+        assert_eq!(vm_from_dmi("Virtual Machine"), Virtualization::HyperV);
+        assert_eq!(vm_from_dmi("innotek GmbH"), Virtualization::VirtualBox);
+        assert_eq!(vm_from_dmi("Microsoft Corporation"), Virtualization::None);
+        assert_eq!(vm_from_dmi("Dell Inc."), Virtualization::None);
+    }
+}
@@ -0,0 +1,247 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::{Pid, ProcessExt, ProcessRefreshKind, System, SystemExt};
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::process::{Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Resources used by a process and its descendants, returned by [`measure`] and
+/// [`measure_pid`].
+///
+/// The memory, I/O and thread values are computed from samples taken while the processes are
+/// running, so short-lived descendants might be missed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Measurement {
+    /// Time elapsed between the start of the measure and the exit of the process.
+    pub wall_time: Duration,
+    /// Time spent in user mode by the process and its waited-for descendants. It is `0` on
+    /// non-unix targets and when the process isn't a child of the current one.
+    pub user_time: Duration,
+    /// Time spent in kernel mode by the process and its waited-for descendants. It is `0` on
+    /// non-unix targets and when the process isn't a child of the current one.
+    pub system_time: Duration,
+    /// Total CPU time of the process and its descendants: the sum of `user_time` and
+    /// `system_time` when they're available, otherwise computed from the samples.
+    pub cpu_time: Duration,
+    /// Highest memory usage (in bytes) of the process and its descendants together.
+    pub peak_memory: u64,
+    /// Number of bytes read from disk by the process and its descendants.
+    pub read_bytes: u64,
+    /// Number of bytes written to disk by the process and its descendants.
+    pub written_bytes: u64,
+    /// Highest number of threads of the process and its descendants together.
+    pub max_threads: usize,
+    /// Exit status of the process. It is `None` if the process isn't a child of the current one.
+    pub exit_status: Option<ExitStatus>,
+}
+
+/// Spawns `command` and samples it and its descendants every `interval` until it exits.
+///
+/// ```no_run
+/// use std::process::Command;
+/// use std::time::Duration;
+///
+/// let m = sysinfo::measure(Command::new("ls").arg("-l"), Duration::from_millis(10))
+///     .expect("failed to run ls");
+/// println!("ls: {:?} wall time, {} peak memory", m.wall_time, sysinfo::Bytes(m.peak_memory));
+/// ```
+pub fn measure(command: &mut Command, interval: Duration) -> io::Result<Measurement> {
+    let start = Instant::now();
+    let child = command.spawn()?;
+    Ok(sample(child.id() as Pid, Some(child), start, interval))
+}
+
+/// Samples the process `pid` and its descendants every `interval` until it exits.
+///
+/// The process should be a child of the current process, otherwise its exit status and CPU
+/// times (which are retrieved by waiting for it) aren't available.
+///
+/// ```no_run
+/// use std::process::Command;
+/// use std::time::Duration;
+///
+/// let child = Command::new("ls").spawn().expect("failed to run ls");
+/// let m = sysinfo::measure_pid(child.id() as sysinfo::Pid, Duration::from_millis(10));
+/// println!("ls: {:?} CPU time", m.cpu_time);
+/// ```
+pub fn measure_pid(pid: Pid, interval: Duration) -> Measurement {
+    sample(pid, None, Instant::now(), interval)
+}
+
+/// Maximum values seen for each process of the tree.
+#[derive(Default)]
+struct Totals {
+    cpu_time: HashMap<Pid, Duration>,
+    read_bytes: HashMap<Pid, u64>,
+    written_bytes: HashMap<Pid, u64>,
+}
+
+fn update_max<T: Ord + Copy>(map: &mut HashMap<Pid, T>, pid: Pid, value: T) {
+    let entry = map.entry(pid).or_insert(value);
+    if *entry < value {
+        *entry = value;
+    }
+}
+
+fn descendants(s: &System, pid: Pid) -> HashSet<Pid> {
+    let mut tree = HashSet::new();
+    tree.insert(pid);
+    // Parents can have a bigger pid than their children (when pids wrap around), so we loop
+    // until no new descendant is found.
+    loop {
+        let len = tree.len();
+        for process in s.processes().values() {
+            if process.parent().map(|p| tree.contains(&p)).unwrap_or(false) {
+                tree.insert(process.pid());
+            }
+        }
+        if tree.len() == len {
+            return tree;
+        }
+    }
+}
+
+/// Returns the children of `pid`, or `None` if they cannot be retrieved without going through
+/// all the processes.
+fn children(pid: Pid) -> Option<Vec<Pid>> {
+    cfg_if::cfg_if! {
+        if #[cfg(all(any(target_os = "linux", target_os = "android"), not(feature = "unknown-ci")))] {
+            crate::sys::process::get_children(pid)
+        } else {
+            let _ = pid;
+            None
+        }
+    }
+}
+
+/// Adds the new descendants to `tree` and refreshes its processes. The processes which exited
+/// are removed from it.
+fn refresh_tree(s: &mut System, tree: &mut HashSet<Pid>, refresh_kind: ProcessRefreshKind) {
+    let mut parents = tree.iter().copied().collect::<Vec<_>>();
+    while !parents.is_empty() {
+        let mut new = Vec::new();
+        for parent in parents {
+            match children(parent) {
+                Some(children) => new.extend(children.into_iter().filter(|c| tree.insert(*c))),
+                None => {
+                    // We have to go through all the processes to find the descendants.
+                    s.refresh_processes_specifics(refresh_kind);
+                    for pid in tree.iter().copied().collect::<Vec<_>>() {
+                        tree.extend(descendants(s, pid));
+                    }
+                    tree.retain(|pid| s.process(*pid).is_some());
+                    return;
+                }
+            }
+        }
+        parents = new;
+    }
+    s.refresh_processes_list_specifics(&tree.iter().copied().collect::<Vec<_>>(), refresh_kind);
+    tree.retain(|pid| s.process(*pid).is_some());
+}
+
+// On unix targets, the process is waited with `wait4` directly to get its resources usage.
+#[cfg_attr(unix, allow(unused_variables, unused_mut))]
+fn sample(
+    pid: Pid,
+    mut child: Option<std::process::Child>,
+    start: Instant,
+    interval: Duration,
+) -> Measurement {
+    let refresh_kind = ProcessRefreshKind::new()
+        .with_cpu()
+        .with_memory()
+        .with_disk_usage();
+    let mut s = System::new();
+    let mut m = Measurement::default();
+    let mut totals = Totals::default();
+    let mut tree = HashSet::new();
+    tree.insert(pid);
+
+    loop {
+        // Only the processes of the tree are refreshed.
+        refresh_tree(&mut s, &mut tree, refresh_kind);
+        let (mut memory, mut threads) = (0, 0);
+        for p in tree.iter().filter_map(|pid| s.process(*pid)) {
+            let disk_usage = p.disk_usage();
+
+            memory += p.memory();
+            threads += p.thread_count();
+            update_max(&mut totals.cpu_time, p.pid(), p.total_cpu_time());
+            update_max(&mut totals.read_bytes, p.pid(), disk_usage.total_read_bytes);
+            update_max(
+                &mut totals.written_bytes,
+                p.pid(),
+                disk_usage.total_written_bytes,
+            );
+        }
+        m.peak_memory = m.peak_memory.max(memory);
+        m.max_threads = m.max_threads.max(threads);
+
+        cfg_if::cfg_if! {
+            if #[cfg(unix)] {
+                let exited = wait(pid, &mut m);
+            } else {
+                let exited = match child {
+                    Some(ref mut child) => match child.try_wait() {
+                        Ok(Some(status)) => {
+                            m.exit_status = Some(status);
+                            true
+                        }
+                        Ok(None) => false,
+                        Err(_) => true,
+                    },
+                    None => s.process(pid).is_none(),
+                };
+            }
+        }
+        if exited {
+            break;
+        }
+        thread::sleep(interval);
+    }
+    m.wall_time = start.elapsed();
+    m.read_bytes = totals.read_bytes.values().sum();
+    m.written_bytes = totals.written_bytes.values().sum();
+    if m.cpu_time == Duration::default() {
+        m.cpu_time = totals.cpu_time.values().sum();
+    }
+    m
+}
+
+/// Returns `true` if the process has exited. If it was a child of the current process, it is
+/// reaped and its resources usage is stored into `m`.
+#[cfg(unix)]
+fn wait(pid: Pid, m: &mut Measurement) -> bool {
+    use crate::ResourceUsage;
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut status = 0;
+    let mut rusage = unsafe { std::mem::zeroed::<libc::rusage>() };
+
+    match unsafe { libc::wait4(pid as _, &mut status, libc::WNOHANG, &mut rusage) } {
+        0 => false,
+        -1 => {
+            if io::Error::last_os_error().raw_os_error() == Some(libc::EINTR) {
+                return false;
+            }
+            // It isn't a child of the current process, so we can only check if it still exists.
+            let ret = unsafe { libc::kill(pid as _, 0) };
+            ret != 0 && io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH)
+        }
+        _ => {
+            m.exit_status = Some(ExitStatus::from_raw(status));
+            let usage = ResourceUsage::from_rusage(&rusage);
+            m.user_time = usage.user_time;
+            m.system_time = usage.system_time;
+            m.cpu_time = usage.cpu_time();
+            // `ru_maxrss` is the peak of the biggest process, which might have been missed by
+            // the samples.
+            m.peak_memory = m.peak_memory.max(usage.peak_memory);
+            true
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::ProcessStatus;
+
+use std::time::Instant;
+
+/// Information about a process at a given time, returned by
+/// [`ProcessMonitor::sample`][crate::ProcessMonitor::sample].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProcessSample {
+    /// When the sample was taken. On linux, it is retrieved using `CLOCK_MONOTONIC`.
+    pub timestamp: Instant,
+    /// CPU usage (in %) since the previous sample, `0.` for the first one.
+    pub cpu_usage: f32,
+    /// CPU usage (in %) spent in user mode since the previous sample.
+    pub user_cpu_usage: f32,
+    /// CPU usage (in %) spent in kernel mode since the previous sample.
+    pub system_cpu_usage: f32,
+    /// Memory usage (in bytes).
+    pub memory: u64,
+    /// Virtual memory usage (in bytes).
+    pub virtual_memory: u64,
+    /// Highest memory usage (in bytes) of the process so far.
+    pub peak_memory: u64,
+    /// Total number of bytes read from disk by the process.
+    pub read_bytes: u64,
+    /// Total number of bytes written to disk by the process.
+    pub written_bytes: u64,
+    /// Number of threads of the process.
+    pub thread_count: usize,
+    /// Status of the process.
+    pub status: ProcessStatus,
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(all(any(target_os = "linux", target_os = "android"), not(feature = "unknown-ci")))] {
+        pub use crate::sys::monitor::ProcessMonitor;
+    } else {
+        use crate::{Pid, ProcessExt, ProcessRefreshKind, System, SystemExt};
+
+        /// Monitors a single process to sample its resources usage at a high frequency.
+        ///
+        /// On linux, the files of the process are kept open and samples are taken without
+        /// allocation. On other targets, it refreshes the process with
+        /// [`SystemExt::refresh_process_specifics`].
+        ///
+        /// ```no_run
+        /// use sysinfo::ProcessMonitor;
+        ///
+        /// let mut monitor = ProcessMonitor::new(1337).expect("process not found");
+        /// while let Some(sample) = monitor.sample() {
+        ///     println!("{}% {} bytes", sample.cpu_usage, sample.memory);
+        ///     std::thread::sleep(std::time::Duration::from_millis(1));
+        /// }
+        /// ```
+        pub struct ProcessMonitor {
+            pid: Pid,
+            system: System,
+            peak_memory: u64,
+        }
+
+        impl ProcessMonitor {
+            /// Starts monitoring the process `pid`. Returns `None` if it doesn't exist.
+            pub fn new(pid: Pid) -> Option<ProcessMonitor> {
+                let mut system = System::new();
+                if !system.refresh_process_specifics(pid, Self::refresh_kind()) {
+                    return None;
+                }
+                Some(ProcessMonitor {
+                    pid,
+                    system,
+                    peak_memory: 0,
+                })
+            }
+
+            fn refresh_kind() -> ProcessRefreshKind {
+                ProcessRefreshKind::new()
+                    .with_cpu()
+                    .with_memory()
+                    .with_disk_usage()
+            }
+
+            /// Returns the pid of the monitored process.
+            pub fn pid(&self) -> Pid {
+                self.pid
+            }
+
+            /// Takes a new sample. Returns `None` if the process doesn't exist anymore.
+            pub fn sample(&mut self) -> Option<ProcessSample> {
+                if !self
+                    .system
+                    .refresh_process_specifics(self.pid, Self::refresh_kind())
+                {
+                    return None;
+                }
+                let timestamp = Instant::now();
+                let p = self.system.process(self.pid)?;
+                let disk_usage = p.disk_usage();
+
+                self.peak_memory = self.peak_memory.max(p.memory());
+                Some(ProcessSample {
+                    timestamp,
+                    cpu_usage: p.cpu_usage(),
+                    user_cpu_usage: p.user_cpu_usage(),
+                    system_cpu_usage: p.system_cpu_usage(),
+                    memory: p.memory(),
+                    virtual_memory: p.virtual_memory(),
+                    peak_memory: self.peak_memory,
+                    read_bytes: disk_usage.total_read_bytes,
+                    written_bytes: disk_usage.total_written_bytes,
+                    thread_count: p.thread_count(),
+                    status: p.status(),
+                })
+            }
+        }
+    }
+}
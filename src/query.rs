@@ -0,0 +1,514 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::{DiskUsage, Pid, Process, ProcessExt, ProcessStatus, Uid, User, UserExt};
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::PathBuf;
+
+/// Metric used to sort the processes returned by a [`ProcessQuery`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessSortKey {
+    /// Sort by [`ProcessExt::pid`].
+    Pid,
+    /// Sort by [`ProcessExt::name`].
+    Name,
+    /// Sort by [`ProcessExt::cpu_usage`].
+    CpuUsage,
+    /// Sort by [`ProcessExt::total_cpu_time`].
+    TotalCpuTime,
+    /// Sort by [`ProcessExt::memory`].
+    Memory,
+    /// Sort by [`ProcessExt::virtual_memory`].
+    VirtualMemory,
+    /// Sort by [`ProcessExt::started_at`].
+    StartTime,
+    /// Sort by [`DiskUsage::read_bytes`][crate::DiskUsage::read_bytes].
+    DiskRead,
+    /// Sort by [`DiskUsage::written_bytes`][crate::DiskUsage::written_bytes].
+    DiskWritten,
+    /// Sort by [`DiskUsage::total_read_bytes`][crate::DiskUsage::total_read_bytes].
+    TotalDiskRead,
+    /// Sort by [`DiskUsage::total_written_bytes`][crate::DiskUsage::total_written_bytes].
+    TotalDiskWritten,
+}
+
+impl ProcessSortKey {
+    fn compare(self, a: &Process, b: &Process) -> Ordering {
+        match self {
+            ProcessSortKey::Pid => a.pid().cmp(&b.pid()),
+            ProcessSortKey::Name => a.name().cmp(b.name()),
+            ProcessSortKey::CpuUsage => a
+                .cpu_usage()
+                .partial_cmp(&b.cpu_usage())
+                .unwrap_or(Ordering::Equal),
+            ProcessSortKey::TotalCpuTime => a.total_cpu_time().cmp(&b.total_cpu_time()),
+            ProcessSortKey::Memory => a.memory().cmp(&b.memory()),
+            ProcessSortKey::VirtualMemory => a.virtual_memory().cmp(&b.virtual_memory()),
+            ProcessSortKey::StartTime => a.started_at().cmp(&b.started_at()),
+            ProcessSortKey::DiskRead => a.disk_usage().read_bytes.cmp(&b.disk_usage().read_bytes),
+            ProcessSortKey::DiskWritten => a
+                .disk_usage()
+                .written_bytes
+                .cmp(&b.disk_usage().written_bytes),
+            ProcessSortKey::TotalDiskRead => a
+                .disk_usage()
+                .total_read_bytes
+                .cmp(&b.disk_usage().total_read_bytes),
+            ProcessSortKey::TotalDiskWritten => a
+                .disk_usage()
+                .total_written_bytes
+                .cmp(&b.disk_usage().total_written_bytes),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum NameFilter {
+    Exact(String),
+    Contains(String),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl NameFilter {
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            NameFilter::Exact(s) => name == s,
+            NameFilter::Contains(s) => name.contains(s.as_str()),
+            #[cfg(feature = "regex")]
+            NameFilter::Regex(r) => r.is_match(name),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum UserFilter {
+    Id(Uid),
+    Name(String),
+}
+
+/// Builder used to filter and sort processes.
+///
+/// Filters are combined: a process is only returned if it matches all of them. It is run with
+/// [`SystemExt::query_processes`][crate::SystemExt::query_processes].
+///
+/// Only the information retrieved by the last processes refresh is used. For example, filtering
+/// on the user requires [`ProcessRefreshKind::user`][crate::ProcessRefreshKind::user] and
+/// filtering on the user name also requires the users list to be loaded (with
+/// [`SystemExt::refresh_users_list`][crate::SystemExt::refresh_users_list]).
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, ProcessQuery, ProcessSortKey, System, SystemExt};
+///
+/// let s = System::new_all();
+/// // The 10 processes using the most memory for the "postgres" user.
+/// let query = ProcessQuery::new()
+///     .user_name("postgres")
+///     .sort_by_desc(ProcessSortKey::Memory)
+///     .top_n(10);
+/// for process in s.query_processes(&query) {
+///     println!("[{}] {} {} bytes", process.pid(), process.name(), process.memory());
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ProcessQuery {
+    name: Option<NameFilter>,
+    exe: Option<PathBuf>,
+    cmd_arg: Option<String>,
+    user: Option<UserFilter>,
+    parent: Option<Pid>,
+    status: Option<ProcessStatus>,
+    cgroup: Option<PathBuf>,
+    min_cpu_usage: Option<f32>,
+    max_cpu_usage: Option<f32>,
+    min_memory: Option<u64>,
+    max_memory: Option<u64>,
+    sort: Option<(ProcessSortKey, bool)>,
+    top_n: Option<usize>,
+}
+
+impl ProcessQuery {
+    /// Creates a new `ProcessQuery` matching all processes.
+    ///
+    /// ```
+    /// use sysinfo::{ProcessQuery, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// assert_eq!(s.query_processes(&ProcessQuery::new()).len(), s.processes().len());
+    /// ```
+    pub fn new() -> ProcessQuery {
+        ProcessQuery::default()
+    }
+
+    /// Only keeps processes whose name is exactly `name`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().name("bash");
+    /// ```
+    pub fn name<S: Into<String>>(mut self, name: S) -> ProcessQuery {
+        self.name = Some(NameFilter::Exact(name.into()));
+        self
+    }
+
+    /// Only keeps processes whose name contains `name`, like
+    /// [`SystemExt::process_by_name`][crate::SystemExt::process_by_name].
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().name_contains("kworker");
+    /// ```
+    pub fn name_contains<S: Into<String>>(mut self, name: S) -> ProcessQuery {
+        self.name = Some(NameFilter::Contains(name.into()));
+        self
+    }
+
+    /// Only keeps processes whose name matches the `regex`. It requires the `regex` feature.
+    ///
+    /// ```
+    /// # extern crate regex_crate as regex;
+    /// use regex::Regex;
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().name_regex(Regex::new("^python[0-9.]*$").unwrap());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn name_regex(mut self, regex: regex::Regex) -> ProcessQuery {
+        self.name = Some(NameFilter::Regex(regex));
+        self
+    }
+
+    /// Only keeps processes whose executable path is `exe`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().exe("/usr/bin/bash");
+    /// ```
+    pub fn exe<P: Into<PathBuf>>(mut self, exe: P) -> ProcessQuery {
+        self.exe = Some(exe.into());
+        self
+    }
+
+    /// Only keeps processes which have `arg` in their command line.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().cmd_arg("--verbose");
+    /// ```
+    pub fn cmd_arg<S: Into<String>>(mut self, arg: S) -> ProcessQuery {
+        self.cmd_arg = Some(arg.into());
+        self
+    }
+
+    /// Only keeps processes owned by the user with the ID `uid`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessQuery, System, SystemExt, UserExt};
+    ///
+    /// let s = System::new_all();
+    /// let query = ProcessQuery::new().user_id(s.users()[0].uid());
+    /// ```
+    pub fn user_id(mut self, uid: Uid) -> ProcessQuery {
+        self.user = Some(UserFilter::Id(uid));
+        self
+    }
+
+    /// Only keeps processes owned by the user named `name`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().user_name("root");
+    /// ```
+    pub fn user_name<S: Into<String>>(mut self, name: S) -> ProcessQuery {
+        self.user = Some(UserFilter::Name(name.into()));
+        self
+    }
+
+    /// Only keeps the processes whose parent is `parent`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().parent(1);
+    /// ```
+    pub fn parent(mut self, parent: Pid) -> ProcessQuery {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Only keeps the processes with the given `status`.
+    ///
+    /// ```
+    /// use sysinfo::{ProcessQuery, ProcessStatus};
+    ///
+    /// let query = ProcessQuery::new().status(ProcessStatus::Run);
+    /// ```
+    pub fn status(mut self, status: ProcessStatus) -> ProcessQuery {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only keeps the processes in the control group `cgroup` or in one of its descendants.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().cgroup("/system.slice");
+    /// ```
+    pub fn cgroup<P: Into<PathBuf>>(mut self, cgroup: P) -> ProcessQuery {
+        self.cgroup = Some(cgroup.into());
+        self
+    }
+
+    /// Only keeps the processes using at least `cpu_usage`% of CPU.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().min_cpu_usage(10.);
+    /// ```
+    pub fn min_cpu_usage(mut self, cpu_usage: f32) -> ProcessQuery {
+        self.min_cpu_usage = Some(cpu_usage);
+        self
+    }
+
+    /// Only keeps the processes using at most `cpu_usage`% of CPU.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().max_cpu_usage(1.);
+    /// ```
+    pub fn max_cpu_usage(mut self, cpu_usage: f32) -> ProcessQuery {
+        self.max_cpu_usage = Some(cpu_usage);
+        self
+    }
+
+    /// Only keeps the processes using at least `memory` bytes.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().min_memory(1 << 30);
+    /// ```
+    pub fn min_memory(mut self, memory: u64) -> ProcessQuery {
+        self.min_memory = Some(memory);
+        self
+    }
+
+    /// Only keeps the processes using at most `memory` bytes.
+    ///
+    /// ```
+    /// use sysinfo::ProcessQuery;
+    ///
+    /// let query = ProcessQuery::new().max_memory(1 << 20);
+    /// ```
+    pub fn max_memory(mut self, memory: u64) -> ProcessQuery {
+        self.max_memory = Some(memory);
+        self
+    }
+
+    /// Sorts the processes by `key` in ascending order.
+    ///
+    /// ```
+    /// use sysinfo::{ProcessQuery, ProcessSortKey};
+    ///
+    /// let query = ProcessQuery::new().sort_by(ProcessSortKey::StartTime);
+    /// ```
+    pub fn sort_by(mut self, key: ProcessSortKey) -> ProcessQuery {
+        self.sort = Some((key, false));
+        self
+    }
+
+    /// Sorts the processes by `key` in descending order.
+    ///
+    /// ```
+    /// use sysinfo::{ProcessQuery, ProcessSortKey};
+    ///
+    /// let query = ProcessQuery::new().sort_by_desc(ProcessSortKey::CpuUsage);
+    /// ```
+    pub fn sort_by_desc(mut self, key: ProcessSortKey) -> ProcessQuery {
+        self.sort = Some((key, true));
+        self
+    }
+
+    /// Only returns the `n` first processes (after sorting). If no sort is set, the processes are
+    /// sorted by pid so the same ones are returned each time.
+    ///
+    /// ```
+    /// use sysinfo::{ProcessQuery, ProcessSortKey};
+    ///
+    /// let query = ProcessQuery::new().sort_by_desc(ProcessSortKey::Memory).top_n(10);
+    /// ```
+    pub fn top_n(mut self, n: usize) -> ProcessQuery {
+        self.top_n = Some(n);
+        self
+    }
+
+    fn matches(&self, process: &Process, uids: Option<&[Uid]>) -> bool {
+        if let Some(ref name) = self.name {
+            if !name.matches(process.name()) {
+                return false;
+            }
+        }
+        if let Some(ref exe) = self.exe {
+            if process.exe() != exe {
+                return false;
+            }
+        }
+        if let Some(ref arg) = self.cmd_arg {
+            if !process.cmd().iter().any(|a| a == arg) {
+                return false;
+            }
+        }
+        if let Some(uids) = uids {
+            match process.user_id() {
+                Some(uid) if uids.contains(&uid) => {}
+                _ => return false,
+            }
+        }
+        if self.parent.is_some() && process.parent() != self.parent {
+            return false;
+        }
+        if let Some(status) = self.status {
+            if process.status() != status {
+                return false;
+            }
+        }
+        if let Some(ref cgroup) = self.cgroup {
+            if process.cgroup().as_os_str().is_empty() || !process.cgroup().starts_with(cgroup) {
+                return false;
+            }
+        }
+        let cpu_usage = process.cpu_usage();
+        if self
+            .min_cpu_usage
+            .map(|min| cpu_usage < min)
+            .unwrap_or(false)
+            || self
+                .max_cpu_usage
+                .map(|max| cpu_usage > max)
+                .unwrap_or(false)
+        {
+            return false;
+        }
+        let memory = process.memory();
+        !(self.min_memory.map(|min| memory < min).unwrap_or(false)
+            || self.max_memory.map(|max| memory > max).unwrap_or(false))
+    }
+
+    pub(crate) fn run<'a>(
+        &self,
+        processes: &'a HashMap<Pid, Process>,
+        users: &[User],
+    ) -> Vec<&'a Process> {
+        let uids = match self.user {
+            Some(UserFilter::Id(uid)) => Some(vec![uid]),
+            Some(UserFilter::Name(ref name)) => Some(
+                users
+                    .iter()
+                    .filter(|u| u.name() == name)
+                    .map(|u| u.uid())
+                    .collect(),
+            ),
+            None => None,
+        };
+        let mut ret = processes
+            .values()
+            .filter(|p| self.matches(p, uids.as_deref()))
+            .collect::<Vec<_>>();
+
+        match self.sort {
+            Some((key, true)) => ret.sort_by(|a, b| key.compare(b, a)),
+            Some((key, false)) => ret.sort_by(|a, b| key.compare(a, b)),
+            // The processes are stored in a `HashMap` so their order isn't stable.
+            None if self.top_n.is_some() => ret.sort_unstable_by_key(|p| p.pid()),
+            None => {}
+        }
+        if let Some(n) = self.top_n {
+            ret.truncate(n);
+        }
+        ret
+    }
+}
+
+/// Resources used by a group of processes.
+///
+/// It is returned by [`SystemExt::group_processes_by_user`],
+/// [`SystemExt::group_processes_by_exe`], [`SystemExt::group_processes_by_name`] and
+/// [`SystemExt::group_processes_by_cgroup`].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (uid, group) in s.group_processes_by_user() {
+///     println!(
+///         "{:?}: {} processes, {}% CPU, {} bytes",
+///         uid, group.processes, group.cpu_usage, group.memory,
+///     );
+/// }
+/// ```
+///
+/// [`SystemExt::group_processes_by_user`]: crate::SystemExt::group_processes_by_user
+/// [`SystemExt::group_processes_by_exe`]: crate::SystemExt::group_processes_by_exe
+/// [`SystemExt::group_processes_by_name`]: crate::SystemExt::group_processes_by_name
+/// [`SystemExt::group_processes_by_cgroup`]: crate::SystemExt::group_processes_by_cgroup
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ProcessGroup {
+    /// Sum of the CPU usage (in %) of the processes.
+    pub cpu_usage: f32,
+    /// Sum of the memory usage (in bytes) of the processes.
+    pub memory: u64,
+    /// Sum of the virtual memory usage (in bytes) of the processes.
+    pub virtual_memory: u64,
+    /// Sum of the disk usage of the processes.
+    pub disk_usage: DiskUsage,
+    /// Number of processes in the group.
+    pub processes: usize,
+    /// Sum of the number of threads of the processes.
+    pub threads: usize,
+}
+
+impl ProcessGroup {
+    fn add(&mut self, process: &Process) {
+        let disk_usage = process.disk_usage();
+
+        self.cpu_usage += process.cpu_usage();
+        self.memory += process.memory();
+        self.virtual_memory += process.virtual_memory();
+        self.disk_usage.read_bytes += disk_usage.read_bytes;
+        self.disk_usage.total_read_bytes += disk_usage.total_read_bytes;
+        self.disk_usage.written_bytes += disk_usage.written_bytes;
+        self.disk_usage.total_written_bytes += disk_usage.total_written_bytes;
+        self.processes += 1;
+        self.threads += process.thread_count();
+    }
+}
+
+/// Groups `processes` using the key returned by `key`. Processes for which it returns `None` are
+/// ignored.
+pub(crate) fn group_processes<K, F>(
+    processes: &HashMap<Pid, Process>,
+    key: F,
+) -> HashMap<K, ProcessGroup>
+where
+    K: Eq + Hash,
+    F: Fn(&Process) -> Option<K>,
+{
+    let mut groups: HashMap<K, ProcessGroup> = HashMap::new();
+
+    for process in processes.values() {
+        if let Some(k) = key(process) {
+            groups.entry(k).or_default().add(process);
+        }
+    }
+    groups
+}
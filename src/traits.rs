@@ -2,16 +2,21 @@
 
 use crate::{
     common::{Gid, Uid},
+    query::group_processes,
     sys::{Component, Disk, Networks, Process, Processor},
 };
 use crate::{
-    DiskType, DiskUsage, LoadAvg, NetworksIter, Pid, ProcessStatus, RefreshKind, Signal, User,
+    Cgroups, DiskType, DiskUsage, HardwareInfo, KernelVersion, LoadAvg, LoginRecord, MemoryInfo,
+    NetworksIter, NumaNode, OsRelease, Pid, Pressure, ProcessGroup, ProcessQuery,
+    ProcessRefreshKind, ProcessStatus, RefreshKind, Session, Signal, StaleFile, Swap, SystemdUnit,
+    User, Virtualization, VmStats,
 };
 
 use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fmt::Debug;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 /// Contains all the methods of the [`Disk`][crate::Disk] struct.
 ///
@@ -141,6 +146,44 @@ pub trait ProcessExt: Debug {
     /// ```
     fn kill(&self, signal: Signal) -> bool;
 
+    /// Returns the executable and shared libraries of the process which have been deleted or
+    /// replaced on disk since the process loaded them. Such a process needs to be restarted to
+    /// use the new version of the files (after a security update for example).
+    ///
+    /// Unlike the other methods, the information is retrieved when this method is called. It
+    /// always returns an empty list on non-linux targets.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.processes().values() {
+    ///     if !process.stale_files().is_empty() {
+    ///         println!("{} needs to be restarted", process.name());
+    ///     }
+    /// }
+    /// ```
+    fn stale_files(&self) -> Vec<StaleFile>;
+
+    /// Returns the memory (in bytes) of the process on each NUMA node, indexed by the node
+    /// identifier. It is read from `/proc/[pid]/numa_maps`.
+    ///
+    /// Unlike the other methods, the information is retrieved when this method is called (and
+    /// reading it can be slow for big processes). It always returns an empty map on non-linux
+    /// targets.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.process(1337) {
+    ///     for (node, memory) in process.numa_memory() {
+    ///         println!("node {}: {} bytes", node, memory);
+    ///     }
+    /// }
+    /// ```
+    fn numa_memory(&self) -> HashMap<usize, u64>;
+
     /// Returns the name of the process.
     ///
     /// **⚠️ Important ⚠️**
@@ -234,26 +277,26 @@ pub trait ProcessExt: Debug {
     /// ```
     fn root(&self) -> &Path;
 
-    /// Returns the memory usage (in KB).
+    /// Returns the memory usage (in bytes).
     ///
     /// ```no_run
     /// use sysinfo::{ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
     /// if let Some(process) = s.process(1337) {
-    ///     println!("{} KB", process.memory());
+    ///     println!("{} bytes", process.memory());
     /// }
     /// ```
     fn memory(&self) -> u64;
 
-    /// Returns the virtual memory usage (in KB).
+    /// Returns the virtual memory usage (in bytes).
     ///
     /// ```no_run
     /// use sysinfo::{ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
     /// if let Some(process) = s.process(1337) {
-    ///     println!("{} KB", process.virtual_memory());
+    ///     println!("{} bytes", process.virtual_memory());
     /// }
     /// ```
     fn virtual_memory(&self) -> u64;
@@ -294,6 +337,33 @@ pub trait ProcessExt: Debug {
     /// ```
     fn start_time(&self) -> u64;
 
+    /// Returns the time of process launch.
+    ///
+    /// Unlike [`start_time`][ProcessExt::start_time], it keeps the sub-second precision when
+    /// the platform provides it.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("Started at {:?}", process.started_at());
+    /// }
+    /// ```
+    fn started_at(&self) -> SystemTime;
+
+    /// Returns for how long the process has been running.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("Running for {:?}", process.run_time());
+    /// }
+    /// ```
+    fn run_time(&self) -> Duration;
+
     /// Returns the total CPU usage (in %). Notice that it might be bigger than 100 if run on a
     /// multicore machine.
     ///
@@ -313,6 +383,103 @@ pub trait ProcessExt: Debug {
     /// ```
     fn cpu_usage(&self) -> f32;
 
+    /// Returns the part of [`cpu_usage`][ProcessExt::cpu_usage] spent in user mode (in %).
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{}%", process.user_cpu_usage());
+    /// }
+    /// ```
+    fn user_cpu_usage(&self) -> f32;
+
+    /// Returns the part of [`cpu_usage`][ProcessExt::cpu_usage] spent in kernel mode (in %).
+    ///
+    /// A high value here usually means that the process is doing a lot of system calls.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{}%", process.system_cpu_usage());
+    /// }
+    /// ```
+    fn system_cpu_usage(&self) -> f32;
+
+    /// Returns the total CPU time (user and system) consumed by the process since it started.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{:?}", process.total_cpu_time());
+    /// }
+    /// ```
+    fn total_cpu_time(&self) -> Duration;
+
+    /// Returns the number of threads of the process (or `0` if it isn't available).
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{} threads", process.thread_count());
+    /// }
+    /// ```
+    fn thread_count(&self) -> usize;
+
+    /// Returns the ID of the owner of the process.
+    ///
+    /// It returns `None` if the information isn't available (on Windows for example) or hasn't
+    /// been retrieved yet.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     eprintln!("User id for process 1337: {:?}", process.user_id());
+    /// }
+    /// ```
+    fn user_id(&self) -> Option<Uid>;
+
+    /// Returns the path of the control group of the process, relative to the cgroup hierarchy
+    /// mount point (`/user.slice/user-1000.slice/session-2.scope` for example).
+    ///
+    /// When the system uses both cgroup v1 and v2, the v2 hierarchy is preferred. If there is no
+    /// unified hierarchy, the `name=systemd` one is used. It returns an empty path if the
+    /// information isn't available (it is only retrieved on Linux).
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{}", process.cgroup().display());
+    /// }
+    /// ```
+    fn cgroup(&self) -> &Path;
+
+    /// Returns `true` if the process is a kernel thread.
+    ///
+    /// Kernel threads have no command line nor executable. It always returns `false` on
+    /// non-linux targets.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.processes().values().filter(|p| !p.is_kernel_thread()) {
+    ///     println!("{:?}", process.cmd());
+    /// }
+    /// ```
+    fn is_kernel_thread(&self) -> bool;
+
     /// Returns number of bytes read and written to disk.
     ///
     /// /!\\ On Windows, this method actually returns **ALL** I/O read and written bytes.
@@ -400,6 +567,18 @@ pub trait ProcessorExt: Debug {
     /// }
     /// ```
     fn frequency(&self) -> u64;
+
+    /// Returns the identifier of the NUMA node of the processor. It is only available on linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.processors() {
+    ///     println!("{}: {:?}", processor.name(), processor.numa_node());
+    /// }
+    /// ```
+    fn numa_node(&self) -> Option<usize>;
 }
 
 /// Contains all the methods of the [`System`][crate::System] type.
@@ -589,7 +768,19 @@ pub trait SystemExt: Sized + Debug + Default {
     /// let mut s = System::new_all();
     /// s.refresh_processes();
     /// ```
-    fn refresh_processes(&mut self);
+    fn refresh_processes(&mut self) {
+        self.refresh_processes_specifics(ProcessRefreshKind::everything());
+    }
+
+    /// Gets all processes and updates the specified information.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_processes_specifics(ProcessRefreshKind::new().with_cpu().with_memory());
+    /// ```
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind);
 
     /// Refreshes *only* the process corresponding to `pid`. Returns `false` if the process doesn't
     /// exist. If it isn't listed yet, it'll be added.
@@ -600,7 +791,54 @@ pub trait SystemExt: Sized + Debug + Default {
     /// let mut s = System::new_all();
     /// s.refresh_process(1337);
     /// ```
-    fn refresh_process(&mut self, pid: Pid) -> bool;
+    fn refresh_process(&mut self, pid: Pid) -> bool {
+        self.refresh_process_specifics(pid, ProcessRefreshKind::everything())
+    }
+
+    /// Refreshes *only* the process corresponding to `pid` with the specified information.
+    /// Returns `false` if the process doesn't exist. If it isn't listed yet, it'll be added.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_process_specifics(1337, ProcessRefreshKind::new().with_cpu());
+    /// ```
+    fn refresh_process_specifics(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool;
+
+    /// Refreshes *only* the processes corresponding to `pids`. The ones which aren't listed yet
+    /// will be added and the ones which don't exist anymore will be removed.
+    ///
+    /// Unlike calling [`refresh_process`][SystemExt::refresh_process] on each of them, the CPU
+    /// usage of all the processes is computed against the same CPU time snapshot.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_processes_list(&[1337, 1338]);
+    /// ```
+    fn refresh_processes_list(&mut self, pids: &[Pid]) {
+        self.refresh_processes_list_specifics(pids, ProcessRefreshKind::everything());
+    }
+
+    /// Refreshes *only* the processes corresponding to `pids` with the specified information.
+    /// The ones which aren't listed yet will be added and the ones which don't exist anymore will
+    /// be removed.
+    ///
+    /// If you don't need the processes' threads to be updated, use
+    /// [`ProcessRefreshKind::without_threads`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_processes_list_specifics(
+    ///     &[1337, 1338],
+    ///     ProcessRefreshKind::everything().without_threads(),
+    /// );
+    /// ```
+    fn refresh_processes_list_specifics(&mut self, pids: &[Pid], refresh_kind: ProcessRefreshKind);
 
     /// Refreshes the listed disks' information.
     ///
@@ -681,6 +919,41 @@ pub trait SystemExt: Sized + Debug + Default {
         self.networks_mut().refresh_networks_list();
     }
 
+    /// Refreshes the statistics of the control groups.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_cgroups_list();
+    /// s.refresh_cgroups();
+    /// ```
+    ///
+    /// This is a shortcut for:
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.cgroups_mut().refresh();
+    /// ```
+    fn refresh_cgroups(&mut self) {
+        self.cgroups_mut().refresh();
+    }
+
+    /// The control groups list will be updated: removing the ones which don't exist anymore and
+    /// adding new ones. It is only supported on linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_cgroups_list();
+    /// ```
+    fn refresh_cgroups_list(&mut self) {
+        self.cgroups_mut().refresh_cgroups_list();
+    }
+
     /// Refreshes all system, processes, disks and network interfaces information.
     ///
     /// Please note that it doesn't recompute disks list, components list, network interfaces
@@ -743,6 +1016,118 @@ pub trait SystemExt: Sized + Debug + Default {
         ret
     }
 
+    /// Returns the processes matching the given `query`, sorted as requested.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, ProcessQuery, ProcessSortKey, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let query = ProcessQuery::new().sort_by_desc(ProcessSortKey::CpuUsage).top_n(5);
+    /// for process in s.query_processes(&query) {
+    ///     println!("{} {}%", process.name(), process.cpu_usage());
+    /// }
+    /// ```
+    fn query_processes(&self, query: &ProcessQuery) -> Vec<&Process> {
+        query.run(self.processes(), self.users())
+    }
+
+    /// Groups the processes by the ID of their owner and returns the resources used by each group.
+    ///
+    /// Processes whose owner isn't known are ignored (on Windows, it means all of them).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (uid, group) in s.group_processes_by_user() {
+    ///     println!("{:?}: {}% CPU, {} bytes", uid, group.cpu_usage, group.memory);
+    /// }
+    /// ```
+    fn group_processes_by_user(&self) -> HashMap<Uid, ProcessGroup> {
+        group_processes(self.processes(), |p| p.user_id())
+    }
+
+    /// Groups the processes by executable path and returns the resources used by each group.
+    ///
+    /// Processes without executable path are ignored.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (exe, group) in s.group_processes_by_exe() {
+    ///     println!("{}: {} processes", exe.display(), group.processes);
+    /// }
+    /// ```
+    fn group_processes_by_exe(&self) -> HashMap<PathBuf, ProcessGroup> {
+        group_processes(self.processes(), |p| {
+            if p.exe().as_os_str().is_empty() {
+                None
+            } else {
+                Some(p.exe().to_path_buf())
+            }
+        })
+    }
+
+    /// Groups the processes by name and returns the resources used by each group.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (name, group) in s.group_processes_by_name() {
+    ///     println!("{}: {} threads", name, group.threads);
+    /// }
+    /// ```
+    fn group_processes_by_name(&self) -> HashMap<String, ProcessGroup> {
+        group_processes(self.processes(), |p| Some(p.name().to_owned()))
+    }
+
+    /// Groups the processes by control group and returns the resources used by each group.
+    ///
+    /// Processes are only counted in the control group they belong to, not in its ancestors.
+    /// Processes without control group are ignored (on non-linux targets, it means all of them).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (cgroup, group) in s.group_processes_by_cgroup() {
+    ///     println!("{}: {} bytes", cgroup.display(), group.memory);
+    /// }
+    /// ```
+    fn group_processes_by_cgroup(&self) -> HashMap<PathBuf, ProcessGroup> {
+        group_processes(self.processes(), |p| {
+            if p.cgroup().as_os_str().is_empty() {
+                None
+            } else {
+                Some(p.cgroup().to_path_buf())
+            }
+        })
+    }
+
+    /// Returns the systemd units owning processes, with the resources used by their processes
+    /// and their limits.
+    ///
+    /// The unit of each process is computed from its control group, so
+    /// [`ProcessRefreshKind::cgroup`][crate::ProcessRefreshKind::cgroup] must have been
+    /// refreshed. It doesn't use D-Bus: only units with processes are listed. It returns an
+    /// empty list if the system hasn't been booted with systemd (and on non-linux targets).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for unit in s.systemd_units() {
+    ///     // Each process has at least one thread so they are already counted in `threads`.
+    ///     println!(
+    ///         "{}: {} tasks (limit: {:?})",
+    ///         unit.name, unit.usage.threads, unit.tasks_limit,
+    ///     );
+    /// }
+    /// ```
+    fn systemd_units(&self) -> Vec<SystemdUnit>;
+
     /// Returns "global" processors information (aka the addition of all the processors).
     ///
     /// ```no_run
@@ -779,17 +1164,17 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn physical_core_count(&self) -> Option<usize>;
 
-    /// Returns the RAM size in KB.
+    /// Returns the RAM size in bytes.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// println!("{} KB", s.total_memory());
+    /// println!("{} bytes", s.total_memory());
     /// ```
     fn total_memory(&self) -> u64;
 
-    /// Returns the amount of free RAM in KB.
+    /// Returns the amount of free RAM in bytes.
     ///
     /// Generally, "free" memory refers to unallocated memory whereas "available" memory refers to
     /// memory that is available for (re)use.
@@ -801,11 +1186,11 @@ pub trait SystemExt: Sized + Debug + Default {
     /// use sysinfo::{System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// println!("{} KB", s.free_memory());
+    /// println!("{} bytes", s.free_memory());
     /// ```
     fn free_memory(&self) -> u64;
 
-    /// Returns the amount of available RAM in KB.
+    /// Returns the amount of available RAM in bytes.
     ///
     /// Generally, "free" memory refers to unallocated memory whereas "available" memory refers to
     /// memory that is available for (re)use.
@@ -817,50 +1202,188 @@ pub trait SystemExt: Sized + Debug + Default {
     /// use sysinfo::{System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// println!("{} KB", s.available_memory());
+    /// println!("{} bytes", s.available_memory());
     /// ```
     fn available_memory(&self) -> u64;
 
-    /// Returns the amound of used RAM in KB.
+    /// Returns the amound of used RAM in bytes.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// println!("{} KB", s.used_memory());
+    /// println!("{} bytes", s.used_memory());
     /// ```
     fn used_memory(&self) -> u64;
 
-    /// Returns the SWAP size in KB.
+    /// Returns the SWAP size in bytes.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// println!("{} KB", s.total_swap());
+    /// println!("{} bytes", s.total_swap());
     /// ```
     fn total_swap(&self) -> u64;
 
-    /// Returns the amount of free SWAP in KB.
+    /// Returns the amount of free SWAP in bytes.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// println!("{} KB", s.free_swap());
+    /// println!("{} bytes", s.free_swap());
     /// ```
     fn free_swap(&self) -> u64;
 
-    /// Returns the amount of used SWAP in KB.
+    /// Returns the amount of used SWAP in bytes.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// println!("{} KB", s.used_swap());
+    /// println!("{} bytes", s.used_swap());
     /// ```
     fn used_swap(&self) -> u64;
 
+    /// Returns detailed memory information.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Bytes, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let info = s.memory_info();
+    /// println!("{} committed out of {}", Bytes(info.committed_as), Bytes(info.commit_limit));
+    /// ```
+    fn memory_info(&self) -> &MemoryInfo;
+
+    /// Returns the amount of RAM (in bytes) the current process can use.
+    ///
+    /// On linux, it is the lowest memory limit of the control group of the current process and
+    /// its ancestors (`memory.max` with cgroup v2, `memory.limit_in_bytes` with cgroup v1) if it
+    /// is lower than [`total_memory`](#tymethod.total_memory). This is what limits the memory of
+    /// a container. On other targets, it is the same as `total_memory`.
+    ///
+    /// Unlike [`total_memory`](#tymethod.total_memory), the control group files are read when
+    /// this method is called.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.effective_memory_limit());
+    /// ```
+    fn effective_memory_limit(&self) -> u64 {
+        self.total_memory()
+    }
+
+    /// Returns the amount of used RAM (in bytes), out of
+    /// [`effective_memory_limit`](#method.effective_memory_limit).
+    ///
+    /// On linux, when the memory is limited by a control group, it is the memory usage of this
+    /// control group (`memory.current` with cgroup v2, `memory.usage_in_bytes` with cgroup v1).
+    /// Otherwise, it is the same as [`used_memory`](#tymethod.used_memory).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!(
+    ///     "{} bytes used out of {} bytes",
+    ///     s.effective_memory_usage(),
+    ///     s.effective_memory_limit(),
+    /// );
+    /// ```
+    fn effective_memory_usage(&self) -> u64 {
+        self.used_memory()
+    }
+
+    /// Returns the number of CPUs the current process can use, which is useful to size a thread
+    /// pool.
+    ///
+    /// On linux, it is limited by the CPUs of the control group of the current process
+    /// (`cpuset.cpus.effective` with cgroup v2, `cpuset.effective_cpus` with cgroup v1) and by
+    /// its CPU quota (`cpu.max` with cgroup v2, `cpu.cfs_quota_us` and `cpu.cfs_period_us` with
+    /// cgroup v1), rounded up. On other targets, it is the number of
+    /// [`processors`](#tymethod.processors).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// println!("{} CPUs", s.effective_cpu_count());
+    /// ```
+    fn effective_cpu_count(&self) -> usize {
+        self.processors().len()
+    }
+
+    /// Returns the virtual memory statistics, updated by
+    /// [`refresh_memory`](#tymethod.refresh_memory).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let stats = s.vm_stats();
+    /// println!("{} major page faults per second", stats.pgmajfault.per_second);
+    /// ```
+    fn vm_stats(&self) -> &VmStats;
+
+    /// Returns the list of the swap devices, read from `/proc/swaps`.
+    ///
+    /// It is only available on linux, otherwise it returns an empty list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for swap in s.swaps() {
+    ///     println!("{}: {:?} {} bytes", swap.path.display(), swap.type_, swap.size);
+    /// }
+    /// ```
+    fn swaps(&self) -> Vec<Swap>;
+
+    /// Returns the NUMA nodes of the system, read from `/sys/devices/system/node`.
+    ///
+    /// It is only available on linux, otherwise it returns an empty list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for node in s.numa_nodes() {
+    ///     println!("node {}: {} remote allocations", node.id, node.numa_miss);
+    /// }
+    /// ```
+    fn numa_nodes(&self) -> Vec<NumaNode>;
+
+    /// Returns the virtual machine or container the system is running in, detected like
+    /// `systemd-detect-virt` does.
+    ///
+    /// It is only available on linux, otherwise it returns [`Virtualization::None`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// println!("virtualization: {:?}", s.virtualization());
+    /// ```
+    fn virtualization(&self) -> Virtualization;
+
+    /// Returns the identity of the machine (vendor, model, serial number...), read from
+    /// `/sys/class/dmi/id`.
+    ///
+    /// It is only available on linux, otherwise all the fields are empty.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// let hardware = s.hardware_info();
+    /// println!("{} {}", hardware.system_vendor, hardware.product_name);
+    /// ```
+    fn hardware_info(&self) -> HardwareInfo;
+
     /// Returns the components list.
     ///
     /// ```no_run
@@ -909,6 +1432,45 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn users(&self) -> &[User];
 
+    /// Returns the users currently logged in, read from `/var/run/utmp`.
+    ///
+    /// It is only available on linux, otherwise it returns an empty list.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for session in s.sessions() {
+    ///     println!("{} on {} since {}", session.user, session.tty, session.login_time);
+    /// }
+    /// ```
+    fn sessions(&self) -> Vec<Session>;
+
+    /// Returns the past and current sessions, read from `/var/log/wtmp`, oldest first.
+    ///
+    /// It is only available on linux, otherwise it returns an empty list. Reading the file might
+    /// require root access on some systems.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for record in s.login_history() {
+    ///     println!(
+    ///         "{} from {:?}: {} - {:?}",
+    ///         record.session.user,
+    ///         record.session.host,
+    ///         record.session.login_time,
+    ///         record.logout_time,
+    ///     );
+    /// }
+    /// ```
+    fn login_history(&self) -> Vec<LoginRecord>;
+
     /// Returns the disks list.
     ///
     /// ```no_run
@@ -950,6 +1512,30 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn networks_mut(&mut self) -> &mut Networks;
 
+    /// Returns the control groups found by the last call to
+    /// [`SystemExt::refresh_cgroups_list`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_cgroups_list();
+    /// for (path, cgroup) in s.cgroups() {
+    ///     println!("{}: {} tasks", path.display(), cgroup.tasks());
+    /// }
+    /// ```
+    fn cgroups(&self) -> &Cgroups;
+
+    /// Returns a mutable access to the control groups.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.cgroups_mut().refresh_cgroups_list();
+    /// ```
+    fn cgroups_mut(&mut self) -> &mut Cgroups;
+
     /// Returns system uptime (in seconds).
     ///
     /// ```no_run
@@ -986,6 +1572,38 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn load_average(&self) -> LoadAvg;
 
+    /// Returns the system-wide Pressure Stall Information, read from `/proc/pressure`.
+    ///
+    /// It is only available on linux, otherwise all the fields are `None`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// let pressure = s.pressure();
+    /// if let Some(memory) = pressure.memory.and_then(|m| m.full) {
+    ///     println!("memory full stall: {}% over the last minute", memory.avg60);
+    /// }
+    /// ```
+    fn pressure(&self) -> Pressure;
+
+    /// Returns the Pressure Stall Information of the `cgroup` control group (as returned by
+    /// [`ProcessExt::cgroup`]), read from its `cpu.pressure`, `memory.pressure`, `io.pressure` and
+    /// `irq.pressure` files.
+    ///
+    /// It is only available with cgroup v2 on linux, otherwise all the fields are `None`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.process(1337) {
+    ///     let pressure = s.cgroup_pressure(process.cgroup());
+    ///     println!("{:?}", pressure.cpu);
+    /// }
+    /// ```
+    fn cgroup_pressure(&self, cgroup: &Path) -> Pressure;
+
     /// Returns the system name.
     ///
     /// **Important**: this information is computed every time this function is called.
@@ -1034,6 +1652,40 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn long_os_version(&self) -> Option<String>;
 
+    /// Returns the kernel version parsed from [`SystemExt::kernel_version`]. On unix targets,
+    /// the other fields returned by `uname` are filled as well.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(kernel) = s.parsed_kernel_version() {
+    ///     println!("{} {}.{} on {}", kernel.sysname, kernel.major, kernel.minor, kernel.machine);
+    /// }
+    /// ```
+    fn parsed_kernel_version(&self) -> Option<KernelVersion> {
+        KernelVersion::parse(&self.kernel_version()?)
+    }
+
+    /// Returns the identification of the operating system, read from `/etc/os-release` or
+    /// `/usr/lib/os-release`.
+    ///
+    /// It is only available on linux (not android), otherwise it returns `None`.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(os) = s.os_release() {
+    ///     println!("{} (like {:?})", os.id, os.id_like);
+    /// }
+    /// ```
+    fn os_release(&self) -> Option<OsRelease>;
+
     /// Returns the system hostname based off DNS
     ///
     /// **Important**: this information is computed every time this function is called.
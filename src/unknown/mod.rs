@@ -1,5 +1,6 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+pub(crate) mod cgroup;
 pub mod component;
 pub mod disk;
 pub mod network;
@@ -1,9 +1,11 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal, StaleFile, Uid};
 
+use std::collections::HashMap;
 use std::fmt;
 use std::path::Path;
+use std::time::{Duration, SystemTime};
 
 impl fmt::Display for ProcessStatus {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -27,6 +29,14 @@ impl ProcessExt for Process {
         false
     }
 
+    fn stale_files(&self) -> Vec<StaleFile> {
+        Vec::new()
+    }
+
+    fn numa_memory(&self) -> HashMap<usize, u64> {
+        HashMap::new()
+    }
+
     fn name(&self) -> &str {
         ""
     }
@@ -75,10 +85,46 @@ impl ProcessExt for Process {
         0
     }
 
+    fn started_at(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH
+    }
+
+    fn run_time(&self) -> Duration {
+        Duration::default()
+    }
+
     fn cpu_usage(&self) -> f32 {
         0.0
     }
 
+    fn user_cpu_usage(&self) -> f32 {
+        0.0
+    }
+
+    fn system_cpu_usage(&self) -> f32 {
+        0.0
+    }
+
+    fn total_cpu_time(&self) -> Duration {
+        Duration::default()
+    }
+
+    fn thread_count(&self) -> usize {
+        0
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn cgroup(&self) -> &Path {
+        Path::new("")
+    }
+
+    fn is_kernel_thread(&self) -> bool {
+        false
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage::default()
     }
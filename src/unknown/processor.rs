@@ -31,4 +31,8 @@ impl ProcessorExt for Processor {
     fn brand(&self) -> &str {
         ""
     }
+
+    fn numa_node(&self) -> Option<usize> {
+        None
+    }
 }
@@ -2,16 +2,29 @@
 
 use crate::{
     sys::{component::Component, Disk, Networks, Process, Processor},
-    LoadAvg, Pid, RefreshKind, SystemExt, User,
+    Cgroups, HardwareInfo, LoadAvg, LoginRecord, MemoryInfo, NumaNode, OsRelease, Pid, Pressure,
+    ProcessRefreshKind, RefreshKind, Session, Swap, SystemExt, SystemdUnit, User, Virtualization,
+    VmStats,
 };
 
 use std::collections::HashMap;
+use std::path::Path;
 
 #[doc = include_str!("../../md_doc/system.md")]
 pub struct System {
     processes_list: HashMap<Pid, Process>,
     networks: Networks,
+    cgroups: Cgroups,
     global_processor: Processor,
+    memory_info: MemoryInfo,
+    vm_stats: VmStats,
+}
+
+impl System {
+    /// Removes the process `pid` from the list and returns it.
+    pub(crate) fn take_process(&mut self, pid: Pid) -> Option<Process> {
+        self.processes_list.remove(&pid)
+    }
 }
 
 impl SystemExt for System {
@@ -21,7 +34,10 @@ impl SystemExt for System {
         System {
             processes_list: Default::default(),
             networks: Networks::new(),
+            cgroups: Cgroups::new(),
             global_processor: Processor::new(),
+            memory_info: MemoryInfo::default(),
+            vm_stats: VmStats::default(),
         }
     }
 
@@ -31,12 +47,19 @@ impl SystemExt for System {
 
     fn refresh_components_list(&mut self) {}
 
-    fn refresh_processes(&mut self) {}
+    fn refresh_processes_specifics(&mut self, _refresh_kind: ProcessRefreshKind) {}
 
-    fn refresh_process(&mut self, _pid: Pid) -> bool {
+    fn refresh_process_specifics(&mut self, _pid: Pid, _refresh_kind: ProcessRefreshKind) -> bool {
         false
     }
 
+    fn refresh_processes_list_specifics(
+        &mut self,
+        _pids: &[Pid],
+        _refresh_kind: ProcessRefreshKind,
+    ) {
+    }
+
     fn refresh_disks_list(&mut self) {}
 
     fn refresh_users_list(&mut self) {}
@@ -61,6 +84,14 @@ impl SystemExt for System {
         &mut self.networks
     }
 
+    fn cgroups(&self) -> &Cgroups {
+        &self.cgroups
+    }
+
+    fn cgroups_mut(&mut self) -> &mut Cgroups {
+        &mut self.cgroups
+    }
+
     fn global_processor_info(&self) -> &Processor {
         &self.global_processor
     }
@@ -101,6 +132,30 @@ impl SystemExt for System {
         0
     }
 
+    fn memory_info(&self) -> &MemoryInfo {
+        &self.memory_info
+    }
+
+    fn vm_stats(&self) -> &VmStats {
+        &self.vm_stats
+    }
+
+    fn swaps(&self) -> Vec<Swap> {
+        Vec::new()
+    }
+
+    fn numa_nodes(&self) -> Vec<NumaNode> {
+        Vec::new()
+    }
+
+    fn virtualization(&self) -> Virtualization {
+        Virtualization::None
+    }
+
+    fn hardware_info(&self) -> HardwareInfo {
+        HardwareInfo::default()
+    }
+
     fn components(&self) -> &[Component] {
         &[]
     }
@@ -133,10 +188,30 @@ impl SystemExt for System {
         }
     }
 
+    fn pressure(&self) -> Pressure {
+        Pressure::default()
+    }
+
+    fn cgroup_pressure(&self, _cgroup: &Path) -> Pressure {
+        Pressure::default()
+    }
+
     fn users(&self) -> &[User] {
         &[]
     }
 
+    fn sessions(&self) -> Vec<Session> {
+        Vec::new()
+    }
+
+    fn login_history(&self) -> Vec<LoginRecord> {
+        Vec::new()
+    }
+
+    fn systemd_units(&self) -> Vec<SystemdUnit> {
+        Vec::new()
+    }
+
     fn name(&self) -> Option<String> {
         None
     }
@@ -153,6 +228,10 @@ impl SystemExt for System {
         None
     }
 
+    fn os_release(&self) -> Option<OsRelease> {
+        None
+    }
+
     fn host_name(&self) -> Option<String> {
         None
     }
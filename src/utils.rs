@@ -14,6 +14,33 @@ pub(crate) fn to_cpath(path: &std::path::Path) -> Vec<u8> {
     cpath
 }
 
+/// Returns the kernel version along with the other fields of `uname`.
+#[cfg(all(
+    not(feature = "unknown-ci"),
+    any(target_os = "linux", target_os = "android", target_vendor = "apple")
+))]
+pub(crate) fn uname_kernel_version() -> Option<crate::KernelVersion> {
+    let mut raw = std::mem::MaybeUninit::<libc::utsname>::zeroed();
+
+    if unsafe { libc::uname(raw.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let info = unsafe { raw.assume_init() };
+    let to_string = |field: &[std::os::raw::c_char]| {
+        field
+            .iter()
+            .take_while(|c| **c != 0)
+            .map(|c| *c as u8 as char)
+            .collect::<String>()
+    };
+
+    let mut version = crate::KernelVersion::parse(&to_string(&info.release))?;
+    version.sysname = to_string(&info.sysname);
+    version.version = to_string(&info.version);
+    version.machine = to_string(&info.machine);
+    Some(version)
+}
+
 /// Converts the value into a parallel iterator (if the multithread feature is enabled)
 /// Uses the rayon::iter::IntoParallelIterator trait
 #[cfg(all(
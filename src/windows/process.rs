@@ -1,7 +1,8 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal, StaleFile, Uid};
 
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::fmt;
 use std::mem::{size_of, zeroed, MaybeUninit};
@@ -12,6 +13,7 @@ use std::path::{Path, PathBuf};
 use std::process;
 use std::ptr::null_mut;
 use std::str;
+use std::time::{Duration, SystemTime};
 
 use libc::{c_void, memcpy};
 
@@ -98,11 +100,14 @@ pub struct Process {
     cpu_calc_values: CPUsageCalculationValues,
     start_time: u64,
     cpu_usage: f32,
+    user_cpu_usage: f32,
+    system_cpu_usage: f32,
     pub(crate) updated: bool,
     old_read_bytes: u64,
     old_written_bytes: u64,
     read_bytes: u64,
     written_bytes: u64,
+    pub(crate) thread_count: usize,
 }
 
 struct CPUsageCalculationValues {
@@ -238,6 +243,8 @@ impl Process {
                 memory,
                 virtual_memory,
                 cpu_usage: 0.,
+                user_cpu_usage: 0.,
+                system_cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
                 start_time: unsafe { get_start_time(handle) },
                 updated: true,
@@ -245,6 +252,7 @@ impl Process {
                 old_written_bytes: 0,
                 read_bytes: 0,
                 written_bytes: 0,
+                thread_count: 0,
             }
         } else {
             Process {
@@ -261,6 +269,8 @@ impl Process {
                 memory,
                 virtual_memory,
                 cpu_usage: 0.,
+                user_cpu_usage: 0.,
+                system_cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
                 start_time: 0,
                 updated: true,
@@ -268,6 +278,7 @@ impl Process {
                 old_written_bytes: 0,
                 read_bytes: 0,
                 written_bytes: 0,
+                thread_count: 0,
             }
         }
     }
@@ -306,6 +317,8 @@ impl Process {
                 memory: 0,
                 virtual_memory: 0,
                 cpu_usage: 0.,
+                user_cpu_usage: 0.,
+                system_cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
                 start_time: get_start_time(process_handler),
                 updated: true,
@@ -313,6 +326,7 @@ impl Process {
                 old_written_bytes: 0,
                 read_bytes: 0,
                 written_bytes: 0,
+                thread_count: 0,
             }
         }
     }
@@ -337,6 +351,8 @@ impl ProcessExt for Process {
                 memory: 0,
                 virtual_memory: 0,
                 cpu_usage: 0.,
+                user_cpu_usage: 0.,
+                system_cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
                 start_time: 0,
                 updated: true,
@@ -344,6 +360,7 @@ impl ProcessExt for Process {
                 old_written_bytes: 0,
                 read_bytes: 0,
                 written_bytes: 0,
+                thread_count: 0,
             }
         }
     }
@@ -358,6 +375,14 @@ impl ProcessExt for Process {
         }
     }
 
+    fn stale_files(&self) -> Vec<StaleFile> {
+        Vec::new()
+    }
+
+    fn numa_memory(&self) -> HashMap<usize, u64> {
+        HashMap::new()
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -406,10 +431,52 @@ impl ProcessExt for Process {
         self.start_time
     }
 
+    fn started_at(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.start_time)
+    }
+
+    fn run_time(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.started_at())
+            .unwrap_or_default()
+    }
+
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn user_cpu_usage(&self) -> f32 {
+        self.user_cpu_usage
+    }
+
+    fn system_cpu_usage(&self) -> f32 {
+        self.system_cpu_usage
+    }
+
+    fn total_cpu_time(&self) -> Duration {
+        // Process times are expressed in 100-nanosecond units.
+        Duration::from_nanos(
+            (self.cpu_calc_values.old_process_user_cpu + self.cpu_calc_values.old_process_sys_cpu)
+                .saturating_mul(100),
+        )
+    }
+
+    fn thread_count(&self) -> usize {
+        self.thread_count
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn cgroup(&self) -> &Path {
+        Path::new("")
+    }
+
+    fn is_kernel_thread(&self) -> bool {
+        false
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             written_bytes: self.written_bytes - self.old_written_bytes,
@@ -862,15 +929,17 @@ pub(crate) fn compute_cpu_usage(p: &mut Process, nb_processors: u64, _now: ULARG
 
         let denominator = (delta_global_user_time + delta_global_kernel_time) as f64;
 
-        p.cpu_usage = 100.0
-            * ((delta_user_time + delta_sys_time) as f64
-                / if denominator == 0.0 {
-                    p.cpu_usage = 0.0;
-                    return;
-                } else {
-                    denominator
-                }) as f32
-            * nb_processors as f32;
+        if denominator == 0.0 {
+            p.cpu_usage = 0.0;
+            p.user_cpu_usage = 0.0;
+            p.system_cpu_usage = 0.0;
+            return;
+        }
+        p.user_cpu_usage =
+            100.0 * (delta_user_time as f64 / denominator) as f32 * nb_processors as f32;
+        p.system_cpu_usage =
+            100.0 * (delta_sys_time as f64 / denominator) as f32 * nb_processors as f32;
+        p.cpu_usage = p.user_cpu_usage + p.system_cpu_usage;
         p.cpu_calc_values.old_process_user_cpu = user;
         p.cpu_calc_values.old_process_sys_cpu = sys;
         p.cpu_calc_values.old_system_user_cpu = global_user_time;
@@ -906,8 +975,8 @@ pub fn update_memory(p: &mut Process) {
             size_of::<PROCESS_MEMORY_COUNTERS_EX>() as DWORD,
         ) != 0
         {
-            p.memory = (pmc.WorkingSetSize as u64) / 1_000;
-            p.virtual_memory = (pmc.PrivateUsage as u64) / 1_000;
+            p.memory = pmc.WorkingSetSize as u64;
+            p.virtual_memory = pmc.PrivateUsage as u64;
         }
     }
 }
@@ -258,6 +258,10 @@ impl ProcessorExt for Processor {
     fn brand(&self) -> &str {
         &self.brand
     }
+
+    fn numa_node(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl Processor {
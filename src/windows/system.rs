@@ -1,6 +1,10 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use crate::{LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, User};
+use crate::{
+    Cgroups, HardwareInfo, LoadAvg, LoginRecord, MemoryInfo, Networks, NumaNode, OsRelease, Pid,
+    Pressure, ProcessExt, ProcessRefreshKind, RefreshKind, Session, Swap, SystemExt, SystemdUnit,
+    User, Virtualization, VmStats,
+};
 use winapi::um::winreg::HKEY_LOCAL_MACHINE;
 
 use crate::sys::component::{self, Component};
@@ -20,6 +24,7 @@ use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::mem::{size_of, zeroed};
 use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
 use std::slice::from_raw_parts;
 use std::time::SystemTime;
 
@@ -48,12 +53,15 @@ pub struct System {
     mem_available: u64,
     swap_total: u64,
     swap_used: u64,
+    memory_info: MemoryInfo,
+    vm_stats: VmStats,
     global_processor: Processor,
     processors: Vec<Processor>,
     components: Vec<Component>,
     disks: Vec<Disk>,
     query: Option<Query>,
     networks: Networks,
+    cgroups: Cgroups,
     boot_time: u64,
     users: Vec<User>,
 }
@@ -75,6 +83,13 @@ unsafe fn boot_time() -> u64 {
     }
 }
 
+impl System {
+    /// Removes the process `pid` from the list and returns it.
+    pub(crate) fn take_process(&mut self, pid: Pid) -> Option<Process> {
+        self.process_list.remove(&pid)
+    }
+}
+
 impl SystemExt for System {
     const IS_SUPPORTED: bool = true;
 
@@ -87,12 +102,15 @@ impl SystemExt for System {
             mem_available: 0,
             swap_total: 0,
             swap_used: 0,
+            memory_info: MemoryInfo::default(),
+            vm_stats: VmStats::default(),
             global_processor: Processor::new_with_values("Total CPU", vendor_id, brand, 0),
             processors,
             components: Vec::new(),
             disks: Vec::with_capacity(2),
             query: None,
             networks: Networks::new(),
+            cgroups: Cgroups::new(),
             boot_time: unsafe { boot_time() },
             users: Vec::new(),
         };
@@ -155,8 +173,8 @@ impl SystemExt for System {
         unsafe {
             GlobalMemoryStatusEx(&mut mem_info);
         }
-        self.mem_total = auto_cast!(mem_info.ullTotalPhys, u64) / 1_000;
-        self.mem_available = auto_cast!(mem_info.ullAvailPhys, u64) / 1_000;
+        self.mem_total = auto_cast!(mem_info.ullTotalPhys, u64);
+        self.mem_available = auto_cast!(mem_info.ullAvailPhys, u64);
         let mut perf_info: PERFORMANCE_INFORMATION = unsafe { zeroed() };
         if unsafe {
             GetPerformanceInfo(&mut perf_info, size_of::<PERFORMANCE_INFORMATION>() as u32)
@@ -170,9 +188,14 @@ impl SystemExt for System {
                 * perf_info
                     .CommitTotal
                     .saturating_sub(perf_info.PhysicalTotal);
-            self.swap_total = (swap_total / 1000) as u64;
-            self.swap_used = (swap_used / 1000) as u64;
+            self.swap_total = swap_total as u64;
+            self.swap_used = swap_used as u64;
         }
+        self.memory_info.total = self.mem_total;
+        self.memory_info.free = self.mem_available;
+        self.memory_info.available = self.mem_available;
+        self.memory_info.swap_total = self.swap_total;
+        self.memory_info.swap_free = self.swap_total.saturating_sub(self.swap_used);
     }
 
     fn refresh_components_list(&mut self) {
@@ -180,17 +203,24 @@ impl SystemExt for System {
     }
 
     #[allow(clippy::map_entry)]
-    fn refresh_process(&mut self, pid: Pid) -> bool {
+    fn refresh_process_specifics(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
         if self.process_list.contains_key(&pid) {
-            if !refresh_existing_process(self, pid) {
+            if !refresh_existing_process(self, pid, refresh_kind) {
                 self.process_list.remove(&pid);
                 return false;
             }
             true
         } else if let Some(mut p) = Process::new_from_pid(pid) {
-            let system_time = get_system_computation_time();
-            compute_cpu_usage(&mut p, self.processors.len() as u64, system_time);
-            update_disk_usage(&mut p);
+            if refresh_kind.memory() {
+                update_memory(&mut p);
+            }
+            if refresh_kind.cpu() {
+                let system_time = get_system_computation_time();
+                compute_cpu_usage(&mut p, self.processors.len() as u64, system_time);
+            }
+            if refresh_kind.disk_usage() {
+                update_disk_usage(&mut p);
+            }
             self.process_list.insert(pid, p);
             true
         } else {
@@ -198,8 +228,16 @@ impl SystemExt for System {
         }
     }
 
+    fn refresh_processes_list_specifics(&mut self, pids: &[Pid], refresh_kind: ProcessRefreshKind) {
+        // Dead processes are removed by `refresh_process_specifics` and each process' CPU usage
+        // is computed with its own system times so there is nothing else to do.
+        for &pid in pids {
+            self.refresh_process_specifics(pid, refresh_kind);
+        }
+    }
+
     #[allow(clippy::cast_ptr_alignment)]
-    fn refresh_processes(&mut self) {
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
         // Windows 10 notebook requires at least 512KiB of memory to make it in one go
         let mut buffer_size: usize = 512 * 1024;
 
@@ -259,10 +297,17 @@ impl SystemExt for System {
                         let pi = *pi.0;
                         let pid = pi.UniqueProcessId as usize;
                         if let Some(proc_) = (*process_list.0.get()).get_mut(&pid) {
-                            proc_.memory = (pi.WorkingSetSize as u64) / 1_000;
-                            proc_.virtual_memory = (pi.VirtualSize as u64) / 1_000;
-                            compute_cpu_usage(proc_, nb_processors, system_time);
-                            update_disk_usage(proc_);
+                            if refresh_kind.memory() {
+                                proc_.memory = pi.WorkingSetSize as u64;
+                                proc_.virtual_memory = pi.VirtualSize as u64;
+                            }
+                            if refresh_kind.cpu() {
+                                compute_cpu_usage(proc_, nb_processors, system_time);
+                            }
+                            if refresh_kind.disk_usage() {
+                                update_disk_usage(proc_);
+                            }
+                            proc_.thread_count = pi.NumberOfThreads as usize;
                             proc_.updated = true;
                             return None;
                         }
@@ -274,12 +319,17 @@ impl SystemExt for System {
                             } else {
                                 None
                             },
-                            (pi.WorkingSetSize as u64) / 1_000,
-                            (pi.VirtualSize as u64) / 1_000,
+                            pi.WorkingSetSize as u64,
+                            pi.VirtualSize as u64,
                             name,
                         );
-                        compute_cpu_usage(&mut p, nb_processors, system_time);
-                        update_disk_usage(&mut p);
+                        if refresh_kind.cpu() {
+                            compute_cpu_usage(&mut p, nb_processors, system_time);
+                        }
+                        if refresh_kind.disk_usage() {
+                            update_disk_usage(&mut p);
+                        }
+                        p.thread_count = pi.NumberOfThreads as usize;
                         Some(p)
                     })
                     .collect::<Vec<_>>();
@@ -363,6 +413,30 @@ impl SystemExt for System {
         self.swap_used
     }
 
+    fn memory_info(&self) -> &MemoryInfo {
+        &self.memory_info
+    }
+
+    fn vm_stats(&self) -> &VmStats {
+        &self.vm_stats
+    }
+
+    fn swaps(&self) -> Vec<Swap> {
+        Vec::new()
+    }
+
+    fn numa_nodes(&self) -> Vec<NumaNode> {
+        Vec::new()
+    }
+
+    fn virtualization(&self) -> Virtualization {
+        Virtualization::None
+    }
+
+    fn hardware_info(&self) -> HardwareInfo {
+        HardwareInfo::default()
+    }
+
     fn components(&self) -> &[Component] {
         &self.components
     }
@@ -383,6 +457,18 @@ impl SystemExt for System {
         &self.users
     }
 
+    fn sessions(&self) -> Vec<Session> {
+        Vec::new()
+    }
+
+    fn login_history(&self) -> Vec<LoginRecord> {
+        Vec::new()
+    }
+
+    fn systemd_units(&self) -> Vec<SystemdUnit> {
+        Vec::new()
+    }
+
     fn networks(&self) -> &Networks {
         &self.networks
     }
@@ -391,6 +477,14 @@ impl SystemExt for System {
         &mut self.networks
     }
 
+    fn cgroups(&self) -> &Cgroups {
+        &self.cgroups
+    }
+
+    fn cgroups_mut(&mut self) -> &mut Cgroups {
+        &mut self.cgroups
+    }
+
     fn uptime(&self) -> u64 {
         unsafe { GetTickCount64() / 1000 }
     }
@@ -403,6 +497,14 @@ impl SystemExt for System {
         get_load_average()
     }
 
+    fn pressure(&self) -> Pressure {
+        Pressure::default()
+    }
+
+    fn cgroup_pressure(&self, _cgroup: &Path) -> Pressure {
+        Pressure::default()
+    }
+
     fn name(&self) -> Option<String> {
         Some("Windows".to_owned())
     }
@@ -446,6 +548,10 @@ impl SystemExt for System {
             build_number.unwrap_or_default()
         ))
     }
+
+    fn os_release(&self) -> Option<OsRelease> {
+        None
+    }
 }
 
 impl Default for System {
@@ -460,18 +566,24 @@ fn is_proc_running(handle: HANDLE) -> bool {
     !(ret == FALSE || exit_code != STILL_ACTIVE)
 }
 
-fn refresh_existing_process(s: &mut System, pid: Pid) -> bool {
+fn refresh_existing_process(s: &mut System, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
     if let Some(ref mut entry) = s.process_list.get_mut(&(pid as usize)) {
         if !is_proc_running(get_handle(entry)) {
             return false;
         }
-        update_memory(entry);
-        update_disk_usage(entry);
-        compute_cpu_usage(
-            entry,
-            s.processors.len() as u64,
-            get_system_computation_time(),
-        );
+        if refresh_kind.memory() {
+            update_memory(entry);
+        }
+        if refresh_kind.disk_usage() {
+            update_disk_usage(entry);
+        }
+        if refresh_kind.cpu() {
+            compute_cpu_usage(
+                entry,
+                s.processors.len() as u64,
+                get_system_computation_time(),
+            );
+        }
         true
     } else {
         false
@@ -0,0 +1,41 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#[test]
+fn test_cgroups() {
+    use sysinfo::SystemExt;
+
+    let mut s = sysinfo::System::new();
+    assert_eq!(s.cgroups().iter().count(), 0);
+    s.refresh_cgroups_list();
+
+    if !sysinfo::System::IS_SUPPORTED
+        || !cfg!(target_os = "linux")
+        || !std::path::Path::new("/sys/fs/cgroup").is_dir()
+    {
+        assert_eq!(s.cgroups().iter().count(), 0);
+        return;
+    }
+    assert!(s.cgroups().get(std::path::Path::new("/")).is_some());
+    // The current process has to be in one of them.
+    let pid = sysinfo::get_current_pid().expect("failed to get current PID");
+    assert!(s.cgroups().iter().any(|(_, c)| c.pids().contains(&pid)));
+
+    // Nothing should have moved backward.
+    let before = s
+        .cgroups()
+        .iter()
+        .map(|(path, c)| (path.clone(), c.total_cpu_time()))
+        .collect::<Vec<_>>();
+    s.refresh_cgroups();
+    for (path, cpu_time) in before {
+        if let Some(cgroup) = s.cgroups().get(&path) {
+            assert!(cgroup.total_cpu_time() >= cpu_time);
+            assert_eq!(
+                cgroup.cpu_time(),
+                cgroup.total_cpu_time() - cpu_time,
+                "{}",
+                path.display()
+            );
+        }
+    }
+}
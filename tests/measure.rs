@@ -0,0 +1,49 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#[test]
+#[cfg(unix)]
+fn test_measure() {
+    use std::process::Command;
+    use std::time::Duration;
+    use sysinfo::SystemExt;
+
+    let m = sysinfo::measure(
+        Command::new("sh")
+            .arg("-c")
+            .arg("i=0; while [ $i -lt 20000 ]; do i=$((i+1)); done; sleep 0.2"),
+        Duration::from_millis(10),
+    )
+    .expect("failed to run sh");
+    assert!(m.exit_status.expect("no exit status").success());
+    assert!(m.wall_time >= Duration::from_millis(200));
+    assert!(m.cpu_time > Duration::default());
+    assert_eq!(m.cpu_time, m.user_time + m.system_time);
+    assert!(m.peak_memory > 0);
+    if sysinfo::System::IS_SUPPORTED && cfg!(target_os = "linux") {
+        assert!(m.max_threads >= 1);
+    }
+
+    let m = sysinfo::measure(
+        Command::new("sh").arg("-c").arg("exit 3"),
+        Duration::from_millis(10),
+    )
+    .expect("failed to run sh");
+    assert_eq!(m.exit_status.expect("no exit status").code(), Some(3));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_measure_pid() {
+    use std::process::Command;
+    use std::time::Duration;
+
+    let mut child = Command::new("sleep")
+        .arg("0.2")
+        .spawn()
+        .expect("failed to run sleep");
+    let m = sysinfo::measure_pid(child.id() as sysinfo::Pid, Duration::from_millis(10));
+    assert!(m.exit_status.expect("no exit status").success());
+    assert!(m.wall_time >= Duration::from_millis(150));
+    // The process has already been reaped by `measure_pid`.
+    assert!(child.wait().is_err());
+}
@@ -236,3 +236,433 @@ fn cpu_usage_is_not_nan() {
     });
     assert!(checked > 0);
 }
+
+#[test]
+fn test_process_cpu_time_split() {
+    use sysinfo::get_current_pid;
+
+    if !sysinfo::System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let pid = get_current_pid().expect("Failed retrieving current pid.");
+    let mut system = sysinfo::System::new();
+    system.refresh_process(pid);
+
+    // We burn a bit of CPU so the total CPU time cannot be 0.
+    let start = std::time::Instant::now();
+    let mut x = 0u64;
+    while start.elapsed() < std::time::Duration::from_millis(100) {
+        x = x.wrapping_add(1);
+    }
+    assert!(x > 0);
+    system.refresh_process(pid);
+
+    let p = system.process(pid).expect("failed to get process");
+    assert!(p.total_cpu_time() > std::time::Duration::from_secs(0));
+    assert!(!p.user_cpu_usage().is_nan());
+    assert!(!p.system_cpu_usage().is_nan());
+    assert!(p.user_cpu_usage() + p.system_cpu_usage() >= p.cpu_usage() - 0.01);
+}
+
+#[test]
+fn test_process_start_and_run_time() {
+    if !sysinfo::System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let before = std::time::SystemTime::now();
+    let mut p = if cfg!(target_os = "windows") {
+        std::process::Command::new("waitfor")
+            .arg("/t")
+            .arg("3")
+            .arg("RunTimeSignal")
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap()
+    } else {
+        std::process::Command::new("sleep")
+            .arg("3")
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap()
+    };
+    let pid = p.id() as sysinfo::Pid;
+    std::thread::sleep(std::time::Duration::from_millis(250));
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let started_at = s.process(pid).map(|p| p.started_at());
+    s.refresh_processes();
+    let process = s.process(pid).expect("Process not found!");
+    let run_time = process.run_time();
+    let started_at_2 = process.started_at();
+    p.kill().expect("Unable to kill process.");
+
+    // The start time must not change between refreshes.
+    assert_eq!(started_at, Some(started_at_2));
+    // We allow one second of difference for platforms not providing sub-second precision.
+    assert!(
+        started_at_2 + std::time::Duration::from_secs(1) >= before,
+        "{:?} < {:?}",
+        started_at_2,
+        before
+    );
+    assert!(run_time < std::time::Duration::from_secs(10));
+    assert_eq!(
+        process.start_time(),
+        started_at_2
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    );
+}
+
+#[test]
+fn test_process_refresh_kind() {
+    use sysinfo::{get_current_pid, ProcessRefreshKind};
+
+    if !sysinfo::System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let pid = get_current_pid().expect("Failed retrieving current pid.");
+    let mut s = sysinfo::System::new();
+    s.refresh_processes_specifics(ProcessRefreshKind::new());
+    {
+        let p = s.process(pid).expect("failed to get process");
+        if cfg!(target_os = "linux") {
+            assert_eq!(p.memory(), 0);
+            assert!(p.cmd().is_empty());
+        }
+    }
+
+    // Information which wasn't retrieved before must be filled on the next refresh.
+    s.refresh_processes_specifics(ProcessRefreshKind::new().with_memory().with_cmd());
+    let p = s.process(pid).expect("failed to get process");
+    assert!(p.memory() > 0);
+    assert!(!p.cmd().is_empty());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_kernel_threads() {
+    use sysinfo::{get_current_pid, ProcessRefreshKind};
+
+    if !sysinfo::System::IS_SUPPORTED {
+        return;
+    }
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let pid = get_current_pid().expect("Failed retrieving current pid.");
+    assert!(!s
+        .process(pid)
+        .expect("failed to get process")
+        .is_kernel_thread());
+    // `kthreadd` isn't visible in all environments (containers for example).
+    if let Some(p) = s.process(2) {
+        if p.name() == "kthreadd" {
+            assert!(p.is_kernel_thread());
+        }
+    }
+
+    s.refresh_processes_specifics(ProcessRefreshKind::everything().without_kernel_threads());
+    assert!(s.processes().values().all(|p| !p.is_kernel_thread()));
+    assert!(s.process(pid).is_some());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_kernel_threads_listed_by_default() {
+    use sysinfo::ProcessRefreshKind;
+
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let kernel_threads = s
+        .processes()
+        .values()
+        .filter(|p| p.is_kernel_thread())
+        .count();
+
+    // Selecting what to refresh shouldn't remove kernel threads.
+    s.refresh_processes_specifics(ProcessRefreshKind::new().with_cpu());
+    assert!(
+        s.processes()
+            .values()
+            .filter(|p| p.is_kernel_thread())
+            .count()
+            >= kernel_threads
+    );
+
+    // `kthreadd` isn't visible in all environments (containers for example).
+    if s.process(2)
+        .map(|p| p.name() == "kthreadd")
+        .unwrap_or(false)
+    {
+        assert!(s.refresh_process_specifics(2, ProcessRefreshKind::new().with_cpu()));
+        assert!(s.processes().values().any(|p| p.parent() == Some(2)));
+
+        let mut s = sysinfo::System::new();
+        s.refresh_processes_specifics(ProcessRefreshKind::new().with_cpu());
+        assert!(s.process(2).is_some());
+        assert!(s.processes().values().any(|p| p.parent() == Some(2)));
+    }
+}
+
+#[test]
+fn test_refresh_processes_list() {
+    if !sysinfo::System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let spawn = || {
+        if cfg!(target_os = "windows") {
+            std::process::Command::new("waitfor")
+                .arg("/t")
+                .arg("3")
+                .arg("ListSignal")
+                .stdout(std::process::Stdio::null())
+                .spawn()
+                .unwrap()
+        } else {
+            std::process::Command::new("sleep")
+                .arg("3")
+                .stdout(std::process::Stdio::null())
+                .spawn()
+                .unwrap()
+        }
+    };
+    let mut p1 = spawn();
+    let mut p2 = spawn();
+    let pids = [p1.id() as sysinfo::Pid, p2.id() as sysinfo::Pid];
+    std::thread::sleep(std::time::Duration::from_millis(250));
+
+    let mut s = sysinfo::System::new();
+    s.refresh_processes_list(&pids);
+    assert_eq!(s.processes().len(), 2);
+    assert!(s.process(pids[0]).is_some());
+    assert!(s.process(pids[1]).is_some());
+
+    p1.kill().expect("Unable to kill process.");
+    p1.wait().expect("Unable to wait for process.");
+    s.refresh_processes_list(&pids);
+    p2.kill().expect("Unable to kill process.");
+    p2.wait().expect("Unable to wait for process.");
+
+    assert!(s.process(pids[0]).is_none());
+    assert!(s.process(pids[1]).is_some());
+}
+
+#[test]
+fn test_query_processes() {
+    use sysinfo::{get_current_pid, ProcessQuery, ProcessSortKey};
+
+    if !sysinfo::System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let pid = get_current_pid().expect("Failed retrieving current pid.");
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+
+    let all = s.query_processes(&ProcessQuery::new());
+    assert_eq!(all.len(), s.processes().len());
+
+    let name = s.process(pid).expect("failed to get process").name();
+    let found = s.query_processes(&ProcessQuery::new().name(name));
+    assert!(found.iter().any(|p| p.pid() == pid));
+    assert!(found.iter().all(|p| p.name() == name));
+    assert!(s
+        .query_processes(&ProcessQuery::new().name("\u{0}not a process name"))
+        .is_empty());
+    #[cfg(feature = "regex")]
+    {
+        let regex = regex_crate::Regex::new(&format!("^{}$", regex_crate::escape(name))).unwrap();
+        let found = s.query_processes(&ProcessQuery::new().name_regex(regex));
+        assert!(found.iter().any(|p| p.pid() == pid));
+        assert!(found.iter().all(|p| p.name() == name));
+    }
+
+    let top = s.query_processes(
+        &ProcessQuery::new()
+            .sort_by_desc(ProcessSortKey::Memory)
+            .top_n(3),
+    );
+    assert_eq!(top.len(), std::cmp::min(3, s.processes().len()));
+    assert!(top.windows(2).all(|w| w[0].memory() >= w[1].memory()));
+    assert!(s
+        .query_processes(&ProcessQuery::new().min_memory(top[0].memory() + 1))
+        .is_empty());
+
+    // Without a sort, the processes with the smallest pids are returned.
+    let first = s.query_processes(&ProcessQuery::new().top_n(3));
+    let mut pids = s.processes().keys().copied().collect::<Vec<_>>();
+    pids.sort_unstable();
+    pids.truncate(3);
+    assert_eq!(first.iter().map(|p| p.pid()).collect::<Vec<_>>(), pids);
+
+    if cfg!(target_os = "linux") {
+        let me = s.process(pid).unwrap();
+        assert!(me.user_id().is_some());
+        let found = s.query_processes(&ProcessQuery::new().user_id(me.user_id().unwrap()));
+        assert!(found.iter().any(|p| p.pid() == pid));
+        if !me.cgroup().as_os_str().is_empty() {
+            let found = s.query_processes(&ProcessQuery::new().cgroup(me.cgroup()));
+            assert!(found.iter().any(|p| p.pid() == pid));
+        }
+    }
+}
+
+#[test]
+fn test_group_processes() {
+    use sysinfo::get_current_pid;
+
+    if !sysinfo::System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let pid = get_current_pid().expect("Failed retrieving current pid.");
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+
+    let by_name = s.group_processes_by_name();
+    assert_eq!(
+        by_name.values().map(|g| g.processes).sum::<usize>(),
+        s.processes().len()
+    );
+    assert_eq!(
+        by_name.values().map(|g| g.memory).sum::<u64>(),
+        s.processes().values().map(|p| p.memory()).sum::<u64>()
+    );
+    let me = s.process(pid).expect("failed to get process");
+    let group = by_name.get(me.name()).expect("failed to get group");
+    assert!(group.memory >= me.memory());
+
+    if cfg!(target_os = "linux") {
+        assert!(me.thread_count() >= 1);
+        assert!(group.threads >= me.thread_count());
+        let by_user = s.group_processes_by_user();
+        assert!(by_user.get(&me.user_id().unwrap()).unwrap().processes >= 1);
+        let by_exe = s.group_processes_by_exe();
+        assert!(by_exe.get(me.exe()).unwrap().processes >= 1);
+    }
+}
+
+#[test]
+fn test_systemd_units() {
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let units = s.systemd_units();
+    if !cfg!(target_os = "linux") {
+        assert!(units.is_empty());
+    }
+    assert!(units.windows(2).all(|w| w[0].name < w[1].name));
+    for unit in &units {
+        assert!(unit.usage.processes > 0);
+        assert!(unit.cgroup.is_absolute());
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_stale_files() {
+    use sysinfo::StaleFileReason;
+
+    if !sysinfo::System::IS_SUPPORTED {
+        return;
+    }
+    let dir = tempfile::tempdir().expect("failed to create temporary directory");
+    let exe = dir.path().join("sysinfo-sleep");
+    std::fs::copy("/bin/sleep", &exe).expect("failed to copy sleep");
+    let mut child = std::process::Command::new(&exe)
+        .arg("3")
+        .spawn()
+        .expect("failed to run sleep");
+    let pid = child.id() as sysinfo::Pid;
+    std::thread::sleep(std::time::Duration::from_millis(250));
+
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    assert!(s.process(pid).unwrap().stale_files().is_empty());
+
+    std::fs::remove_file(&exe).expect("failed to remove sleep");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.process(pid).unwrap();
+    // " (deleted)" must not be added to the path.
+    assert_eq!(p.exe(), exe);
+    let stale_files = p.stale_files();
+    assert_eq!(stale_files.len(), 1);
+    assert_eq!(stale_files[0].path, exe);
+    assert_eq!(stale_files[0].reason, StaleFileReason::Deleted);
+
+    std::fs::copy("/bin/sleep", &exe).expect("failed to copy sleep");
+    let stale_files = p.stale_files();
+    assert_eq!(stale_files.len(), 1);
+    assert_eq!(stale_files[0].reason, StaleFileReason::Replaced);
+
+    child.kill().expect("Unable to kill process.");
+    child.wait().expect("Unable to wait for process.");
+}
+
+#[test]
+fn test_process_monitor() {
+    use sysinfo::{get_current_pid, ProcessMonitor};
+
+    if !sysinfo::System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let pid = get_current_pid().expect("Failed retrieving current pid.");
+    let mut monitor = ProcessMonitor::new(pid).expect("failed to monitor process");
+    assert_eq!(monitor.pid(), pid);
+    let first = monitor.sample().expect("failed to sample");
+    let start = std::time::Instant::now();
+    let mut x = 0u64;
+    while start.elapsed() < std::time::Duration::from_millis(100) {
+        x = x.wrapping_add(1);
+    }
+    let second = monitor.sample().expect("failed to sample");
+    assert!(x > 0);
+    assert!(second.timestamp > first.timestamp);
+    assert!(second.memory > 0);
+    assert!(second.peak_memory >= second.memory);
+    assert!(second.thread_count >= 1);
+    if cfg!(target_os = "linux") {
+        assert!(second.cpu_usage > 0.);
+    }
+    // The memory is in bytes, like the one of `Process`.
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let memory = s.process(pid).expect("failed to get process").memory();
+    assert!(second.memory > memory / 2 && second.memory < memory * 2);
+
+    let mut child = std::process::Command::new(if cfg!(windows) { "waitfor" } else { "sleep" })
+        .args(if cfg!(windows) {
+            &["/t", "3", "MonitorSignal"][..]
+        } else {
+            &["3"][..]
+        })
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut monitor = ProcessMonitor::new(child.id() as sysinfo::Pid).expect("failed to monitor");
+    assert!(monitor.sample().is_some());
+    child.kill().expect("Unable to kill process.");
+    child.wait().expect("Unable to wait for process.");
+    assert!(monitor.sample().is_none());
+}
+
+#[test]
+fn test_current_process() {
+    if !sysinfo::System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let current = sysinfo::current_process().expect("failed to get the current process");
+    assert_eq!(
+        current.process.pid(),
+        sysinfo::get_current_pid().expect("failed to get current pid")
+    );
+    assert!(current.process.memory() > 0);
+    assert!(!current.process.name().is_empty());
+    if cfg!(unix) {
+        assert!(current.self_usage.cpu_time() > std::time::Duration::default());
+        assert!(current.self_usage.peak_memory > 0);
+    }
+    if cfg!(target_os = "linux") {
+        // At least stdin, stdout and stderr.
+        assert!(current.fd_count >= 3);
+    }
+}
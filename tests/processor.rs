@@ -28,3 +28,41 @@ fn test_physical_core_numbers() {
         assert!(count.unwrap() > 0);
     }
 }
+
+#[test]
+fn test_numa_nodes() {
+    use sysinfo::{ProcessExt, ProcessorExt, SystemExt};
+
+    let s = sysinfo::System::new();
+    let nodes = s.numa_nodes();
+    if !sysinfo::System::IS_SUPPORTED
+        || !cfg!(target_os = "linux")
+        || !std::path::Path::new("/sys/devices/system/node").exists()
+    {
+        assert!(nodes.is_empty());
+        return;
+    }
+    assert!(!nodes.is_empty());
+    for node in &nodes {
+        assert!(node.total_memory > 0);
+        assert_eq!(node.distances.len(), nodes.len());
+    }
+    for processor in s.processors() {
+        let id = processor.numa_node().expect("no NUMA node");
+        let node = nodes
+            .iter()
+            .find(|n| n.id == id)
+            .expect("unknown NUMA node");
+        let cpu = processor.name().trim_start_matches("cpu").parse().unwrap();
+        assert!(node.cpus.contains(&cpu));
+    }
+
+    let mut s = sysinfo::System::new();
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    s.refresh_process(pid);
+    let memory = s
+        .process(pid)
+        .expect("current process not found")
+        .numa_memory();
+    assert!(memory.values().sum::<u64>() > 0);
+}